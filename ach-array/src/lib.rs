@@ -3,6 +3,7 @@
 use ach_cell::Cell;
 pub use ach_cell::Ref;
 use core::ops::Index;
+use util::{Consumer, Error, Producer, Queue};
 
 pub struct Array<T, const N: usize> {
     buf: [Cell<T>; N],
@@ -24,6 +25,10 @@ impl<T, const N: usize> Array<T, N> {
     pub const fn capacity(&self) -> usize {
         Self::CAPACITY
     }
+    /// Number of values, the ones in operation by others are counted if initialized.
+    pub fn len(&self) -> usize {
+        self.buf.iter().filter(|x| x.is_initialized()).count()
+    }
     pub fn is_empty(&self) -> bool {
         self.buf.iter().all(|x| !x.is_initialized())
     }
@@ -81,10 +86,17 @@ impl<'a, T, const N: usize> Iterator for ArrayIterator<'a, T, N> {
         if self.index >= self.vec.capacity() {
             return None;
         }
-        let ret = if self.strict {
-            self.vec[self.index].get()
-        } else {
-            self.vec[self.index].try_get()
+        let cell = &self.vec[self.index];
+        let ret = match cell.try_get() {
+            Err(err) if err.retry && self.strict => {
+                // Nothing can finish the transient value while the current core is in critical section.
+                debug_assert!(
+                    !interrupt::in_critical_section(),
+                    "strict Array::iter spins, it can't be called in critical section"
+                );
+                cell.get()
+            }
+            ret => ret,
         };
         self.index += 1;
         if let Ok(ret) = ret {
@@ -94,3 +106,38 @@ impl<'a, T, const N: usize> Iterator for ArrayIterator<'a, T, N> {
         }
     }
 }
+
+impl<T, const N: usize> Producer<T> for Array<T, N> {
+    fn try_push(&mut self, value: T) -> Result<(), Error<T>> {
+        (&*self).try_push(value)
+    }
+}
+impl<T, const N: usize> Producer<T> for &Array<T, N> {
+    fn try_push(&mut self, value: T) -> Result<(), Error<T>> {
+        self.push(value).map(|_| ()).map_err(Error::full)
+    }
+}
+impl<T, const N: usize> Consumer<T> for Array<T, N> {
+    fn try_pop(&mut self) -> Result<T, Error<()>> {
+        (&*self).try_pop()
+    }
+}
+impl<T, const N: usize> Consumer<T> for &Array<T, N> {
+    fn try_pop(&mut self) -> Result<T, Error<()>> {
+        self.pop().ok_or_else(Error::empty)
+    }
+}
+impl<T, const N: usize> Queue<T> for Array<T, N> {
+    fn capacity(&self) -> usize {
+        N
+    }
+    fn len(&self) -> usize {
+        Array::len(self)
+    }
+    fn is_empty(&self) -> bool {
+        Array::is_empty(self)
+    }
+    fn is_full(&self) -> bool {
+        Array::is_full(self)
+    }
+}
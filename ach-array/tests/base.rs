@@ -29,3 +29,13 @@ fn base() {
     assert!(VEC.pop().is_none());
     assert!(VEC.push(6).is_ok());
 }
+
+#[test]
+fn strict_iter_in_critical_section() {
+    let vec: Array<usize, 3> = Array::new();
+    vec.push(1).unwrap();
+    interrupt::free(|_| {
+        assert_eq!(vec.iter(true).count(), 1);
+        drop(Array::<u8, 2>::new());
+    });
+}
@@ -0,0 +1,38 @@
+use ach_array::Array;
+use util::{Consumer, ErrorKind, Producer, Queue};
+
+fn fill<Q: Producer<usize> + Queue<usize>>(q: &mut Q) {
+    let mut i = 0;
+    while q.try_push(i).is_ok() {
+        i += 1;
+        assert_eq!(q.len(), i);
+    }
+    assert_eq!(i, q.capacity());
+    assert!(q.is_full());
+}
+fn drain<Q: Consumer<usize>>(q: &mut Q) -> usize {
+    let mut n = 0;
+    while q.try_pop().is_ok() {
+        n += 1;
+    }
+    assert_eq!(q.try_pop().unwrap_err().kind, ErrorKind::Empty);
+    n
+}
+
+#[test]
+fn owned() {
+    let mut q: Array<usize, 3> = Array::new();
+    fill(&mut q);
+    assert_eq!(q.try_push(3).unwrap_err().kind, ErrorKind::Full);
+    assert_eq!(drain(&mut q), 3);
+    assert!(Queue::is_empty(&q));
+}
+
+#[test]
+fn shared() {
+    static Q: Array<usize, 4> = Array::new();
+    let mut producer = &Q;
+    let mut consumer = &Q;
+    fill(&mut producer);
+    assert_eq!(drain(&mut consumer), 4);
+}
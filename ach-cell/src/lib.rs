@@ -3,8 +3,11 @@ use core::fmt;
 use core::mem::MaybeUninit;
 use core::ops::Deref;
 use core::ptr;
-use core::sync::atomic::Ordering::{Relaxed, SeqCst};
 use interrupt::CriticalSection;
+use util::sync::{
+    Ordering::{Relaxed, SeqCst},
+    UnsafeCell,
+};
 use util::*;
 
 pub struct Ref<'a, T>(&'a Cell<T>);
@@ -26,7 +29,7 @@ impl<'a, T> Ref<'a, T> {
 impl<'a, T> Deref for Ref<'a, T> {
     type Target = T;
     fn deref(&self) -> &Self::Target {
-        unsafe { self.0.val.assume_init_ref() }
+        unsafe { self.0.val_ref() }
     }
 }
 impl<'a, T: fmt::Debug> fmt::Debug for Ref<'a, T> {
@@ -50,38 +53,62 @@ impl<'a, T> Drop for Ref<'a, T> {
         };
         let will_drop = self.will_remove();
         if old.ref_num() == Ok(1) && will_drop {
-            unsafe { ptr::drop_in_place(self.0.val.as_ptr() as *mut T) };
-            self.0
-                .state
-                .store(MemoryState::Uninitialized.into(), SeqCst);
+            // The value counts as dropped even if its `drop` panics.
+            let _reset = defer(|| {
+                self.0
+                    .state
+                    .store(MemoryState::Uninitialized.into(), SeqCst)
+            });
+            unsafe { self.0.drop_val() };
         }
     }
 }
 
 pub struct Cell<T> {
-    val: MaybeUninit<T>,
+    val: UnsafeCell<MaybeUninit<T>>,
     state: AtomicMemoryRefer,
 }
+// The value is only accessed by the owner of the state, or shared by `Ref`.
+// Sharing it needs `T: Sync`, and it may be taken or dropped by any thread, which needs `T: Send`.
+unsafe impl<T: Send> Send for Cell<T> {}
+unsafe impl<T: Send + Sync> Sync for Cell<T> {}
 impl<T> Default for Cell<T> {
     fn default() -> Self {
         Self::new()
     }
 }
 impl<T> Cell<T> {
-    pub const fn new() -> Self {
-        Cell {
-            val: MaybeUninit::uninit(),
-            state: AtomicMemoryRefer::new(MemoryRefer::new()),
+    util::const_fn! {
+        pub fn new() -> Self {
+            Cell {
+                val: UnsafeCell::new(MaybeUninit::uninit()),
+                state: AtomicMemoryRefer::new(MemoryRefer::new()),
+            }
         }
     }
-    pub const fn new_with(init: T) -> Self {
-        Cell {
-            val: MaybeUninit::new(init),
-            state: AtomicMemoryRefer::new(MemoryRefer::new()),
+    util::const_fn! {
+        pub fn new_with(init: T) -> Self {
+            Cell {
+                val: UnsafeCell::new(MaybeUninit::new(init)),
+                state: AtomicMemoryRefer::new(MemoryRefer::new()),
+            }
         }
     }
-    fn ptr(&self) -> *mut T {
-        self.val.as_ptr() as *mut T
+    #[inline]
+    unsafe fn read_val(&self) -> T {
+        self.val.with(|p| ptr::read(p).assume_init())
+    }
+    #[inline]
+    unsafe fn write_val(&self, value: T) {
+        self.val.with_mut(|p| ptr::write(p, MaybeUninit::new(value)));
+    }
+    #[inline]
+    unsafe fn drop_val(&self) {
+        self.val.with_mut(|p| (*p).assume_init_drop());
+    }
+    #[inline]
+    unsafe fn val_ref(&self) -> &T {
+        self.val.with(|p| (*p).assume_init_ref())
     }
     pub fn is_initialized(&self) -> bool {
         let state = self.state.load(SeqCst);
@@ -117,11 +144,12 @@ impl<T> Cell<T> {
             MemoryState::Uninitialized => Ok(None),
             MemoryState::Initialized | MemoryState::Regaining => {
                 if refer.ref_num() == Ok(0) {
-                    let ret = unsafe { ptr::read(self.ptr()) };
+                    let ret = unsafe { self.read_val() };
                     self.state.store(MemoryState::Uninitialized.into(), SeqCst);
                     Ok(Some(ret))
                 } else {
                     Err(Error {
+                        kind: ErrorKind::Referenced,
                         state: MemoryState::Regaining,
                         input: (),
                         retry: true,
@@ -129,6 +157,7 @@ impl<T> Cell<T> {
                 }
             }
             state => Err(Error {
+                kind: state.into(),
                 state,
                 input: (),
                 retry: state.is_transient(),
@@ -141,13 +170,39 @@ impl<T> Cell<T> {
     ///
     /// Notice: `Spin`
     pub fn take(&self) -> Result<Option<T>, Error<()>> {
-        retry(|_| self.try_take(), ())
+        match self.try_take() {
+            Err(err) if err.retry => {
+                // Nothing can release the state while the current core is in critical section.
+                debug_assert!(
+                    !interrupt::in_critical_section(),
+                    "Cell::take spins, it can't be called in critical section"
+                );
+                retry(|_| self.try_take(), ())
+            }
+            ret => ret,
+        }
+    }
+    /// Same as `take`, but gives up after `attempts` retries.
+    ///
+    /// Returns Err with the input if timeout.
+    pub fn take_for(&self, attempts: usize) -> Result<Option<T>, Error<()>> {
+        retry_with(Bounded::new(Spin, attempts), |_| self.try_take(), ())
+    }
+    /// Same as `take`, but gives up once `clock` reaches `deadline`.
+    ///
+    /// Returns Err with the input if timeout.
+    pub fn take_until<C: Clock>(
+        &self,
+        clock: &C,
+        deadline: C::Instant,
+    ) -> Result<Option<T>, Error<()>> {
+        retry_with(Deadline::new(clock, deadline), |_| self.try_take(), ())
     }
 
     /// # Safety
     /// Calling this when the content is not yet fully initialized causes undefined behavior: it is up to the caller to guarantee that the MaybeUninit<T> really is in an initialized state.
     pub unsafe fn peek(&self) -> &T {
-        self.val.assume_init_ref()
+        self.val_ref()
     }
     /// Tries to get a reference to the value of the Cell.
     ///
@@ -162,6 +217,7 @@ impl<T> Cell<T> {
         }) {
             let state = state.state();
             Err(Error {
+                kind: state.into(),
                 state,
                 input: (),
                 retry: state.is_initializing(),
@@ -178,6 +234,22 @@ impl<T> Cell<T> {
     pub fn get(&self) -> Result<Ref<'_, T>, Error<()>> {
         retry(|_| self.try_get(), ())
     }
+    /// Same as `get`, but gives up after `attempts` retries.
+    ///
+    /// Returns Err with the input if timeout.
+    pub fn get_for(&self, attempts: usize) -> Result<Ref<'_, T>, Error<()>> {
+        retry_with(Bounded::new(Spin, attempts), |_| self.try_get(), ())
+    }
+    /// Same as `get`, but gives up once `clock` reaches `deadline`.
+    ///
+    /// Returns Err with the input if timeout.
+    pub fn get_until<C: Clock>(
+        &self,
+        clock: &C,
+        deadline: C::Instant,
+    ) -> Result<Ref<'_, T>, Error<()>> {
+        retry_with(Deadline::new(clock, deadline), |_| self.try_get(), ())
+    }
 
     /// Sets the value of the Cell to the argument value.
     ///
@@ -192,12 +264,13 @@ impl<T> Cell<T> {
         ) {
             let state = state.state();
             Err(Error {
+                kind: state.into(),
                 state,
                 input: value,
                 retry: state.is_erasing(),
             })
         } else {
-            unsafe { ptr::write(self.ptr(), value) };
+            unsafe { self.write_val(value) };
             self.state.store(MemoryState::Initialized.into(), SeqCst);
             Ok(())
         }
@@ -210,6 +283,23 @@ impl<T> Cell<T> {
     pub fn set(&self, value: T) -> Result<(), Error<T>> {
         retry(|v| self.try_set(v), value)
     }
+    /// Same as `set`, but gives up after `attempts` retries.
+    ///
+    /// Returns Err with the input if timeout.
+    pub fn set_for(&self, value: T, attempts: usize) -> Result<(), Error<T>> {
+        retry_with(Bounded::new(Spin, attempts), |v| self.try_set(v), value)
+    }
+    /// Same as `set`, but gives up once `clock` reaches `deadline`.
+    ///
+    /// Returns Err with the input if timeout.
+    pub fn set_until<C: Clock>(
+        &self,
+        value: T,
+        clock: &C,
+        deadline: C::Instant,
+    ) -> Result<(), Error<T>> {
+        retry_with(Deadline::new(clock, deadline), |v| self.try_set(v), value)
+    }
 
     /// Replaces the contained value with value, and returns the old contained value.
     ///
@@ -238,18 +328,19 @@ impl<T> Cell<T> {
 
         match refer.state() {
             MemoryState::Uninitialized => {
-                unsafe { ptr::write(self.ptr(), value) };
+                unsafe { self.write_val(value) };
                 self.state.store(MemoryState::Initialized.into(), SeqCst);
                 Ok(None)
             }
             MemoryState::Initialized | MemoryState::Regaining => {
                 if refer.ref_num() == Ok(0) {
-                    let ret = unsafe { ptr::read(self.ptr()) };
-                    unsafe { ptr::write(self.ptr(), value) };
+                    let ret = unsafe { self.read_val() };
+                    unsafe { self.write_val(value) };
                     self.state.store(MemoryState::Initialized.into(), SeqCst);
                     Ok(Some(ret))
                 } else {
                     Err(Error {
+                        kind: ErrorKind::Referenced,
                         state: MemoryState::Regaining,
                         input: value,
                         retry: true,
@@ -257,6 +348,7 @@ impl<T> Cell<T> {
                 }
             }
             state => Err(Error {
+                kind: state.into(),
                 state,
                 input: value,
                 retry: state.is_transient(),
@@ -271,6 +363,27 @@ impl<T> Cell<T> {
     pub fn replace(&self, value: T) -> Result<Option<T>, Error<T>> {
         retry(|v| self.try_replace(v), value)
     }
+    /// Same as `replace`, but gives up after `attempts` retries.
+    ///
+    /// Returns Err with the input if timeout.
+    pub fn replace_for(&self, value: T, attempts: usize) -> Result<Option<T>, Error<T>> {
+        retry_with(Bounded::new(Spin, attempts), |v| self.try_replace(v), value)
+    }
+    /// Same as `replace`, but gives up once `clock` reaches `deadline`.
+    ///
+    /// Returns Err with the input if timeout.
+    pub fn replace_until<C: Clock>(
+        &self,
+        value: T,
+        clock: &C,
+        deadline: C::Instant,
+    ) -> Result<Option<T>, Error<T>> {
+        retry_with(
+            Deadline::new(clock, deadline),
+            |v| self.try_replace(v),
+            value,
+        )
+    }
 
     /// Tries to get a reference to the value of the Cell.
     ///
@@ -298,6 +411,10 @@ impl<T: fmt::Debug> fmt::Debug for Cell<T> {
 }
 impl<T> Drop for Cell<T> {
     fn drop(&mut self) {
-        let _ = self.take();
+        // No `Ref` is alive, so the value is either present or already dropped.
+        let state = self.state.load(SeqCst).state();
+        if state.is_initialized() || state.is_regaining() {
+            unsafe { self.drop_val() };
+        }
     }
 }
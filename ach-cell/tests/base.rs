@@ -28,8 +28,62 @@ fn base() {
     assert_eq!(CELL.replace(5).unwrap(), None);
     assert_eq!(CELL.replace(6).unwrap(), Some(5));
     let refer = CELL.get();
-    assert_eq!(CELL.try_replace(7).unwrap_err().input, 7);
+    let err = CELL.try_replace(7).unwrap_err();
+    assert_eq!(err.kind, util::ErrorKind::Referenced);
+    assert_eq!(err.into_inner(), 7);
     drop(refer);
     assert_eq!(CELL.replace(8).unwrap(), Some(6));
     assert_eq!(CELL.take().unwrap(), Some(8));
 }
+
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic(expected = "critical section")]
+fn take_in_critical_section() {
+    let cell = Cell::new();
+    cell.set(1).unwrap();
+    let _r = cell.get().unwrap();
+    let _cs = interrupt::CriticalSection::new();
+    let _ = cell.take();
+}
+
+#[test]
+fn take_free_in_critical_section() {
+    let cell = Cell::new();
+    cell.set(1).unwrap();
+    interrupt::free(|_| {
+        assert_eq!(cell.take().unwrap(), Some(1));
+        assert_eq!(cell.take().unwrap(), None);
+        let dropped = Cell::new();
+        dropped.set(2).unwrap();
+        drop(dropped);
+    });
+}
+
+#[test]
+fn timeout() {
+    use ach_test::Ticks;
+    let clock = Ticks::new();
+    let cell = Cell::new();
+
+    assert_eq!(cell.get_for(0).unwrap_err().kind, util::ErrorKind::Empty);
+    assert!(cell.set_for(1, 0).is_ok());
+    assert_eq!(
+        cell.try_set(2).unwrap_err().kind,
+        util::ErrorKind::AlreadyInitialized
+    );
+    assert_eq!(*cell.get_for(0).unwrap(), 1);
+    assert_eq!(*cell.get_until(&clock, 0).unwrap(), 1);
+
+    let refer = cell.get().unwrap();
+    assert_eq!(cell.take_for(3).unwrap_err().kind, util::ErrorKind::Timeout);
+    assert!(cell.take_until(&clock, 5).is_err());
+    assert_eq!(clock.0.get(), 6);
+    refer.remove();
+    assert_eq!(cell.set_for(2, 3).unwrap_err().input, 2);
+    assert_eq!(cell.replace_until(3, &clock, 10).unwrap_err().input, 3);
+    drop(refer);
+
+    assert_eq!(cell.replace_for(4, 0).unwrap(), None);
+    assert_eq!(cell.take_until(&clock, 0).unwrap(), Some(4));
+}
@@ -0,0 +1,61 @@
+//! Model checked by `RUSTFLAGS="--cfg loom" cargo test --test loom --release`.
+#![cfg(loom)]
+use ach_cell::Cell;
+use loom::sync::Arc;
+use loom::thread;
+
+#[test]
+fn set_get() {
+    loom::model(|| {
+        let cell = Arc::new(Cell::new());
+        let setter = {
+            let cell = cell.clone();
+            thread::spawn(move || cell.try_set(1).unwrap())
+        };
+        if let Ok(v) = cell.try_get() {
+            assert_eq!(*v, 1);
+        }
+        setter.join().unwrap();
+        assert_eq!(*cell.try_get().unwrap(), 1);
+    });
+}
+
+#[test]
+fn take_referenced() {
+    let mut model = loom::model::Builder::new();
+    model.preemption_bound = Some(2);
+    model.check(|| {
+        let cell = Arc::new(Cell::new());
+        cell.try_set(1).unwrap();
+        let reader = {
+            let cell = cell.clone();
+            thread::spawn(move || {
+                if let Ok(v) = cell.try_get() {
+                    assert_eq!(*v, 1);
+                }
+            })
+        };
+        // Spins until the reader drops the reference.
+        assert_eq!(cell.take().unwrap(), Some(1));
+        reader.join().unwrap();
+        assert!(!cell.is_initialized());
+    });
+}
+
+#[test]
+fn replace() {
+    loom::model(|| {
+        let cell = Arc::new(Cell::new());
+        let replacer = {
+            let cell = cell.clone();
+            thread::spawn(move || cell.try_replace(1).ok().flatten())
+        };
+        let old = cell.try_replace(2).ok().flatten();
+        let other = replacer.join().unwrap();
+        let last = cell.take().unwrap();
+        // Every value is returned once, unless a replace gave up.
+        let mut vals: Vec<_> = vec![old, other, last].into_iter().flatten().collect();
+        vals.sort();
+        assert!(vals == [1, 2] || vals == [1] || vals == [2]);
+    });
+}
@@ -0,0 +1,16 @@
+#![cfg(not(loom))]
+use ach_cell::Cell;
+use std::sync::atomic::AtomicU8;
+
+fn assert_send_sync<T: Send + Sync>() {}
+
+#[test]
+fn send_sync() {
+    assert_send_sync::<Cell<AtomicU8>>();
+}
+
+#[test]
+fn compile_fail() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/*.rs");
+}
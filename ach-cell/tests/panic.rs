@@ -0,0 +1,26 @@
+#![cfg(not(loom))]
+use ach_cell::Cell;
+use ach_test::PanicOnDrop;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+#[test]
+fn drop_in_ref() {
+    let cell = Cell::new();
+    cell.try_set(PanicOnDrop::new(true)).unwrap();
+    let r = cell.try_get().unwrap();
+    r.remove();
+    // The last `Ref` drops the value, and the panic mustn't leave the cell `Erasing`.
+    assert!(catch_unwind(AssertUnwindSafe(|| drop(r))).is_err());
+    assert!(!cell.is_initialized());
+    assert!(cell.try_set(PanicOnDrop::new(false)).is_ok());
+    assert!(cell.try_get().is_ok());
+}
+
+#[test]
+fn drop_after_take() {
+    let cell = Cell::new();
+    cell.try_set(PanicOnDrop::new(true)).unwrap();
+    assert!(catch_unwind(AssertUnwindSafe(|| drop(cell.take()))).is_err());
+    assert!(cell.try_set(PanicOnDrop::new(false)).is_ok());
+    assert!(cell.try_replace(PanicOnDrop::new(false)).is_ok());
+}
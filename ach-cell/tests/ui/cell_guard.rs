@@ -0,0 +1,9 @@
+use ach_cell::Cell;
+use std::sync::MutexGuard;
+
+fn assert_sync<T: Sync>() {}
+
+fn main() {
+    // The value may be taken by any thread, so it needs `T: Send`.
+    assert_sync::<Cell<MutexGuard<'static, u8>>>();
+}
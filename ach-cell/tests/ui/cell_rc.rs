@@ -0,0 +1,8 @@
+use ach_cell::Cell;
+use std::rc::Rc;
+
+fn assert_sync<T: Sync>() {}
+
+fn main() {
+    assert_sync::<Cell<Rc<u8>>>();
+}
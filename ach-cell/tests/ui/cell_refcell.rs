@@ -0,0 +1,9 @@
+use ach_cell::Cell;
+use std::cell::RefCell;
+
+fn assert_sync<T: Sync>() {}
+
+fn main() {
+    // `Ref` shares the value, so it needs `T: Sync`.
+    assert_sync::<Cell<RefCell<u8>>>();
+}
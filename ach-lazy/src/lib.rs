@@ -3,13 +3,15 @@ use ach_once::Once;
 use core::mem::MaybeUninit;
 use core::ops::Deref;
 use core::ptr;
-use core::sync::atomic::{AtomicBool, Ordering::SeqCst};
+use util::sync::{AtomicBool, Ordering::SeqCst};
 
 pub struct Lazy<T, F = fn() -> T> {
     val: Once<T>,
     has_val: AtomicBool,
     init: MaybeUninit<F>,
 }
+// `init` is taken and called once by the thread which wins `has_val`, so `F: Send` is enough.
+unsafe impl<T: Send + Sync, F: Send> Sync for Lazy<T, F> {}
 impl<T, F> Lazy<T, F> {
     pub const fn new(f: F) -> Lazy<T, F> {
         Lazy {
@@ -0,0 +1,10 @@
+use ach_lazy::Lazy;
+use std::panic::catch_unwind;
+
+#[test]
+fn init() {
+    static LAZY: Lazy<usize> = Lazy::new(|| panic!("init"));
+    assert!(catch_unwind(|| *LAZY).is_err());
+    // Poisoned, it panics instead of spinning.
+    assert!(catch_unwind(|| *LAZY).is_err());
+}
@@ -0,0 +1,371 @@
+use core::cell::UnsafeCell;
+use core::cmp::Ordering;
+use core::marker::{PhantomData, PhantomPinned};
+use core::ops::Deref;
+use core::pin::Pin;
+use core::ptr;
+use interrupt::CriticalSection;
+use util::sync::{
+    AtomicBool, AtomicPtr, AtomicUsize,
+    Ordering::{Acquire, Relaxed, Release},
+};
+
+/// Node of an intrusive doubly linked list.
+///
+/// It is unlinked from its list automatically, when it drop.
+pub struct Node<'a, T> {
+    val: T,
+    prev: UnsafeCell<*mut Node<'a, T>>,
+    next: UnsafeCell<*mut Node<'a, T>>,
+    list: AtomicPtr<LinkedList<T>>,
+    _list: PhantomData<&'a ()>,
+    _pin: PhantomPinned,
+}
+unsafe impl<'a, T: Send> Send for Node<'a, T> {}
+unsafe impl<'a, T: Sync> Sync for Node<'a, T> {}
+impl<'a, T> Node<'a, T> {
+    util::const_fn! {
+        pub fn new(val: T) -> Self {
+            Self {
+                val,
+                prev: UnsafeCell::new(ptr::null_mut()),
+                next: UnsafeCell::new(ptr::null_mut()),
+                list: AtomicPtr::new(ptr::null_mut()),
+                _list: PhantomData,
+                _pin: PhantomPinned,
+            }
+        }
+    }
+    pub fn is_linked(&self) -> bool {
+        !self.list.load(Acquire).is_null()
+    }
+    /// Removes the node from its list.
+    ///
+    /// Returns false if the node is not linked.
+    pub fn unlink(self: Pin<&mut Self>) -> bool {
+        self.unlink_inner()
+    }
+    fn unlink_inner(&self) -> bool {
+        let list = self.list.load(Acquire);
+        if list.is_null() {
+            return false;
+        }
+        // The node borrows the list for `'a`, so the list outlives it.
+        let list = unsafe { &*list };
+        let guard = list.lock();
+        if !ptr::eq(self.list.load(Relaxed), list) {
+            return false;
+        }
+        unsafe { guard.unlink(self as *const _ as *mut _) };
+        true
+    }
+}
+impl<'a, T> Deref for Node<'a, T> {
+    type Target = T;
+    fn deref(&self) -> &Self::Target {
+        &self.val
+    }
+}
+impl<'a, T> Drop for Node<'a, T> {
+    fn drop(&mut self) {
+        self.unlink_inner();
+    }
+}
+
+struct ListGuard<'a, 'cs, T> {
+    list: &'a LinkedList<T>,
+    _cs: CriticalSection<'cs>,
+}
+impl<'a, 'cs, T> ListGuard<'a, 'cs, T> {
+    fn head<'n>(&self) -> *mut Node<'n, T> {
+        unsafe { (*self.list.head.get()).cast() }
+    }
+    fn tail<'n>(&self) -> *mut Node<'n, T> {
+        unsafe { (*self.list.tail.get()).cast() }
+    }
+    fn set_head<'n>(&self, node: *mut Node<'n, T>) {
+        unsafe { *self.list.head.get() = node.cast() };
+    }
+    fn set_tail<'n>(&self, node: *mut Node<'n, T>) {
+        unsafe { *self.list.tail.get() = node.cast() };
+    }
+    /// # Safety
+    /// `node` must be unlinked and pinned.
+    unsafe fn link_front<'n>(&self, node: &Node<'n, T>) {
+        let node_ptr = node as *const _ as *mut Node<'n, T>;
+        let head = self.head();
+        *node.prev.get() = ptr::null_mut();
+        *node.next.get() = head;
+        if let Some(head) = head.as_ref() {
+            *head.prev.get() = node_ptr;
+        } else {
+            self.set_tail(node_ptr);
+        }
+        self.set_head(node_ptr);
+        node.list.store(self.list as *const _ as *mut _, Release);
+        self.list.len.fetch_add(1, Relaxed);
+    }
+    /// # Safety
+    /// `node` must be unlinked and pinned.
+    unsafe fn link_back<'n>(&self, node: &Node<'n, T>) {
+        let node_ptr = node as *const _ as *mut Node<'n, T>;
+        let tail = self.tail();
+        *node.prev.get() = tail;
+        *node.next.get() = ptr::null_mut();
+        if let Some(tail) = tail.as_ref() {
+            *tail.next.get() = node_ptr;
+        } else {
+            self.set_head(node_ptr);
+        }
+        self.set_tail(node_ptr);
+        node.list.store(self.list as *const _ as *mut _, Release);
+        self.list.len.fetch_add(1, Relaxed);
+    }
+    /// # Safety
+    /// `node` must be unlinked and pinned, `next` must be null or linked in this list.
+    unsafe fn link_before<'n>(&self, node: &Node<'n, T>, next: *mut Node<'n, T>) {
+        let next = match next.as_ref() {
+            Some(next) => next,
+            None => return self.link_back(node),
+        };
+        let node_ptr = node as *const _ as *mut Node<'n, T>;
+        let prev = *next.prev.get();
+        *node.prev.get() = prev;
+        *node.next.get() = next as *const _ as *mut _;
+        *next.prev.get() = node_ptr;
+        if let Some(prev) = prev.as_ref() {
+            *prev.next.get() = node_ptr;
+        } else {
+            self.set_head(node_ptr);
+        }
+        node.list.store(self.list as *const _ as *mut _, Release);
+        self.list.len.fetch_add(1, Relaxed);
+    }
+    /// # Safety
+    /// `node` must be linked in this list.
+    unsafe fn unlink<'n>(&self, node: *mut Node<'n, T>) {
+        let node = &*node;
+        let prev = *node.prev.get();
+        let next = *node.next.get();
+        if let Some(prev) = prev.as_ref() {
+            *prev.next.get() = next;
+        } else {
+            self.set_head(next);
+        }
+        if let Some(next) = next.as_ref() {
+            *next.prev.get() = prev;
+        } else {
+            self.set_tail(prev);
+        }
+        *node.prev.get() = ptr::null_mut();
+        *node.next.get() = ptr::null_mut();
+        self.list.len.fetch_sub(1, Relaxed);
+        self.list.unlinks.fetch_add(1, Relaxed);
+        node.list.store(ptr::null_mut(), Release);
+    }
+}
+impl<'a, 'cs, T> Drop for ListGuard<'a, 'cs, T> {
+    fn drop(&mut self) {
+        self.list.locked.store(false, Release);
+    }
+}
+
+/// Intrusive doubly linked list.
+///
+/// Nodes are linked by pinned reference, and can be unlinked in O(1).
+pub struct LinkedList<T> {
+    head: UnsafeCell<*mut Node<'static, T>>,
+    tail: UnsafeCell<*mut Node<'static, T>>,
+    len: AtomicUsize,
+    /// Number of unlinked nodes, a walk across critical sections restarts when it changes.
+    unlinks: AtomicUsize,
+    locked: AtomicBool,
+}
+unsafe impl<T: Send + Sync> Send for LinkedList<T> {}
+unsafe impl<T: Send + Sync> Sync for LinkedList<T> {}
+impl<T> Default for LinkedList<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl<T> LinkedList<T> {
+    /// Number of nodes compared by `push_sorted_by` in one critical section.
+    const SORTED_STEPS: usize = 8;
+
+    util::const_fn! {
+        pub fn new() -> Self {
+            Self {
+                head: UnsafeCell::new(ptr::null_mut()),
+                tail: UnsafeCell::new(ptr::null_mut()),
+                len: AtomicUsize::new(0),
+                unlinks: AtomicUsize::new(0),
+                locked: AtomicBool::new(false),
+            }
+        }
+    }
+    fn lock(&self) -> ListGuard<'_, '_, T> {
+        let cs = CriticalSection::new();
+        while self
+            .locked
+            .compare_exchange_weak(false, true, Acquire, Relaxed)
+            .is_err()
+        {
+            spin_loop::spin();
+        }
+        ListGuard {
+            list: self,
+            _cs: cs,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len.load(Relaxed)
+    }
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Adds a node to the front of the LinkedList.
+    ///
+    /// Panics if the node is already linked.
+    ///
+    /// Notice: `Spin`
+    pub fn push_front<'a>(&'a self, node: Pin<&mut Node<'a, T>>) {
+        let guard = self.lock();
+        assert!(!node.is_linked());
+        unsafe { guard.link_front(&node) };
+    }
+    /// Adds a node to the back of the LinkedList.
+    ///
+    /// Panics if the node is already linked.
+    ///
+    /// Notice: `Spin`
+    pub fn push_back<'a>(&'a self, node: Pin<&mut Node<'a, T>>) {
+        let guard = self.lock();
+        assert!(!node.is_linked());
+        unsafe { guard.link_back(&node) };
+    }
+    /// Adds a node before the first node which is greater than it, so that
+    /// a list only filled by this method stays ordered by `cmp`.
+    ///
+    /// Sorting lives on this list rather than the singly linked `LinkedList`,
+    /// which is prepended lock-free and can't keep an order alongside concurrent pushers.
+    ///
+    /// The walk compares at most `SORTED_STEPS` nodes per critical section,
+    /// and restarts from the front if a node was unlinked while interrupts were enabled.
+    ///
+    /// Panics if the node is already linked.
+    ///
+    /// Notice: `Spin`
+    pub fn push_sorted_by<'a, F>(&'a self, node: Pin<&mut Node<'a, T>>, mut cmp: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        assert!(!node.is_linked());
+        // Last node which is not greater than `node`, null to start from the front.
+        let mut prev: *mut Node<'a, T> = ptr::null_mut();
+        let mut unlinks = 0;
+        loop {
+            let guard = self.lock();
+            // `prev` may have been unlinked and freed meanwhile, only read it if nothing was unlinked.
+            let mut now = if !prev.is_null() && self.unlinks.load(Relaxed) == unlinks {
+                unsafe { *(*prev).next.get() }
+            } else {
+                guard.head()
+            };
+            unlinks = self.unlinks.load(Relaxed);
+            for _ in 0..Self::SORTED_STEPS {
+                match unsafe { now.as_ref() } {
+                    Some(next) if cmp(&node.val, &next.val) != Ordering::Less => {
+                        prev = now;
+                        now = unsafe { *next.next.get() };
+                    }
+                    _ => return unsafe { guard.link_before(&node, now) },
+                }
+            }
+        }
+    }
+    /// Adds a node before the first node whose key is greater than it, so that
+    /// a list only filled by this method stays ordered by `f`.
+    ///
+    /// Panics if the node is already linked.
+    ///
+    /// Notice: `Spin`
+    pub fn push_sorted_by_key<'a, K, F>(&'a self, node: Pin<&mut Node<'a, T>>, mut f: F)
+    where
+        F: FnMut(&T) -> K,
+        K: Ord,
+    {
+        self.push_sorted_by(node, |a, b| f(a).cmp(&f(b)))
+    }
+    /// Removes a node from the LinkedList in O(1).
+    ///
+    /// Returns false if the node is not linked in this list.
+    ///
+    /// Notice: `Spin`
+    pub fn unlink<'a>(&'a self, node: Pin<&mut Node<'a, T>>) -> bool {
+        if !ptr::eq(node.list.load(Acquire), self) {
+            return false;
+        }
+        node.unlink()
+    }
+
+    /// Removes the first node, and calls `f` with its value before the node can be dropped.
+    ///
+    /// Notice: `Spin`
+    pub fn pop_front_with<R>(&self, f: impl FnOnce(&T) -> R) -> Option<R> {
+        let guard = self.lock();
+        let node = unsafe { guard.head().as_ref()? };
+        let ret = f(&node.val);
+        unsafe { guard.unlink(node as *const _ as *mut Node<'_, T>) };
+        Some(ret)
+    }
+    /// Removes the least node of a list ordered by `push_sorted_by`, and calls `f` with its value
+    /// before the node can be dropped.
+    ///
+    /// The nodes are borrowed by the list, so the value is lent to `f` instead of returned.
+    /// It is the front of an ordered list, so it is O(1).
+    ///
+    /// Notice: `Spin`
+    pub fn pop_min_with<R>(&self, f: impl FnOnce(&T) -> R) -> Option<R> {
+        self.pop_front_with(f)
+    }
+    /// Removes the least node of a list ordered by `push_sorted_by`, and returns a clone of its value.
+    ///
+    /// Notice: `Spin`
+    pub fn pop_min(&self) -> Option<T>
+    where
+        T: Clone,
+    {
+        self.pop_min_with(T::clone)
+    }
+    /// Calls `f` with the value of the first node, without removing it.
+    ///
+    /// Notice: `Spin`
+    pub fn front_with<R>(&self, f: impl FnOnce(&T) -> R) -> Option<R> {
+        let guard = self.lock();
+        let node = unsafe { guard.head().as_ref()? };
+        Some(f(&node.val))
+    }
+    /// Removes the last node, and calls `f` with its value before the node can be dropped.
+    ///
+    /// Notice: `Spin`
+    pub fn pop_back_with<R>(&self, f: impl FnOnce(&T) -> R) -> Option<R> {
+        let guard = self.lock();
+        let node = unsafe { guard.tail().as_ref()? };
+        let ret = f(&node.val);
+        unsafe { guard.unlink(node as *const _ as *mut Node<'_, T>) };
+        Some(ret)
+    }
+    /// Calls `f` with the value of every node, from front to back.
+    ///
+    /// Notice: `Spin`
+    pub fn for_each(&self, mut f: impl FnMut(&T)) {
+        let guard = self.lock();
+        let mut now = guard.head();
+        while let Some(node) = unsafe { now.as_ref() } {
+            f(&node.val);
+            now = unsafe { *node.next.get() };
+        }
+    }
+}
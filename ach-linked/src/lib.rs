@@ -1,8 +1,13 @@
 #![no_std]
+pub mod doubly;
+pub mod stack;
+
+#[cfg(loom)]
+use core::array;
 use core::marker::PhantomPinned;
 use core::ops::{Deref, DerefMut};
 use core::ptr::{self, NonNull};
-use core::sync::atomic::{AtomicPtr, Ordering::Relaxed};
+use util::sync::{AtomicPtr, Ordering::Relaxed};
 
 pub struct Node<T> {
     val: T,
@@ -108,20 +113,31 @@ pub struct LinkedList<T> {
     head: AtomicPtr<Node<T>>,
     will_remove: [AtomicPtr<Node<T>>; 4],
 }
+unsafe impl<T: Send> Send for LinkedList<T> {}
+unsafe impl<T: Send> Sync for LinkedList<T> {}
 impl<T> Default for LinkedList<T> {
     fn default() -> Self {
         Self::new()
     }
 }
 impl<T> LinkedList<T> {
+    #[cfg(not(loom))]
     #[allow(clippy::declare_interior_mutable_const)]
     const NONE_NODE: AtomicPtr<Node<T>> = AtomicPtr::new(ptr::null_mut());
+    #[cfg(not(loom))]
     pub const fn new() -> Self {
         Self {
             head: Self::NONE_NODE,
             will_remove: [Self::NONE_NODE; 4],
         }
     }
+    #[cfg(loom)]
+    pub fn new() -> Self {
+        Self {
+            head: AtomicPtr::new(ptr::null_mut()),
+            will_remove: array::from_fn(|_| AtomicPtr::new(ptr::null_mut())),
+        }
+    }
 
     pub fn is_empty(&self) -> bool {
         let head = self.head.load(Relaxed);
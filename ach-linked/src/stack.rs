@@ -0,0 +1,159 @@
+use core::marker::PhantomData;
+use core::ops::{Deref, DerefMut};
+use core::ptr;
+use util::sync::{
+    AtomicUsize,
+    Ordering::{Acquire, Relaxed, Release},
+    UnsafeCell,
+};
+
+/// `Node::next` of a node which was never pushed, it is followed by the next one in the slice.
+const UNLINKED: usize = usize::MAX;
+
+/// Node of an intrusive stack.
+///
+/// Its value is only reachable by `Popped` once it is handed to a `Stack`.
+pub struct Node<T> {
+    val: UnsafeCell<T>,
+    /// Index of the next node plus one, 0 for none, or `UNLINKED` before the first push.
+    next: AtomicUsize,
+}
+unsafe impl<T: Send> Sync for Node<T> {}
+impl<T> Node<T> {
+    util::const_fn! {
+        pub fn new(val: T) -> Self {
+            Self {
+                val: UnsafeCell::new(val),
+                next: AtomicUsize::new(UNLINKED),
+            }
+        }
+    }
+    pub fn get_mut(&mut self) -> &mut T {
+        self.val.with_mut(|p| unsafe { &mut *p })
+    }
+    pub fn into_inner(self) -> T {
+        self.val.into_inner()
+    }
+}
+
+/// Node popped from a `Stack`.
+///
+/// It only gives access to the value, the link of the node belongs to the stack.
+/// Push it again by `Stack::push`.
+pub struct Popped<'a, T> {
+    node: &'a Node<T>,
+    idx: usize,
+    _val: PhantomData<&'a mut T>,
+}
+impl<'a, T> Deref for Popped<'a, T> {
+    type Target = T;
+    fn deref(&self) -> &Self::Target {
+        self.node.val.with(|p| unsafe { &*p })
+    }
+}
+impl<'a, T> DerefMut for Popped<'a, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.node.val.with_mut(|p| unsafe { &mut *p })
+    }
+}
+
+/// Head of a `Stack`: index of the top node plus one in the low bits, and a cycle in the high bits.
+///
+/// The cycle is increased by every pop, like the cycle of `MemoryRing`, so a pop fails
+/// if the top node was popped and pushed again in the meantime (ABA).
+#[derive(Clone, Copy, PartialEq, Eq)]
+struct Head(usize);
+impl Head {
+    fn new(idx: usize, cycle: usize, idx_bits: u32) -> Self {
+        Self(cycle << idx_bits | idx)
+    }
+    fn idx(&self, idx_bits: u32) -> usize {
+        self.0 & ((1 << idx_bits) - 1)
+    }
+    fn cycle(&self, idx_bits: u32) -> usize {
+        self.0 >> idx_bits
+    }
+}
+
+/// Intrusive lock-free stack (Treiber stack) over a slice of nodes, such as a free-list of buffers.
+///
+/// All nodes of the slice are in the stack at first. The nodes are linked by index,
+/// so the cycle of the head has every bit which the indexes don't need,
+/// whatever the width of addresses.
+pub struct Stack<'a, T> {
+    nodes: &'a [Node<T>],
+    head: AtomicUsize,
+}
+impl<'a, T> Stack<'a, T> {
+    util::const_fn! {
+        pub fn new(nodes: &'a [Node<T>]) -> Self {
+            Self {
+                nodes,
+                head: AtomicUsize::new(if nodes.is_empty() { 0 } else { 1 }),
+            }
+        }
+    }
+    fn idx_bits(&self) -> u32 {
+        usize::BITS - self.nodes.len().leading_zeros()
+    }
+
+    /// Number of nodes, in or out of the Stack.
+    pub fn capacity(&self) -> usize {
+        self.nodes.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        Head(self.head.load(Relaxed)).idx(self.idx_bits()) == 0
+    }
+
+    /// Adds a popped node to the top of the Stack.
+    ///
+    /// Panics if the node was popped from another Stack.
+    pub fn push(&self, node: Popped<'a, T>) {
+        assert!(ptr::eq(node.node, &self.nodes[node.idx]));
+        let idx_bits = self.idx_bits();
+        let mut head = Head(self.head.load(Relaxed));
+        loop {
+            node.node.next.store(head.idx(idx_bits), Relaxed);
+            let new = Head::new(node.idx + 1, head.cycle(idx_bits), idx_bits);
+            match self
+                .head
+                .compare_exchange_weak(head.0, new.0, Release, Relaxed)
+            {
+                Ok(_) => return,
+                Err(h) => head = Head(h),
+            }
+        }
+    }
+
+    /// Removes the top node and returns it.
+    ///
+    /// Returns None if the Stack is empty.
+    pub fn pop(&self) -> Option<Popped<'a, T>> {
+        let idx_bits = self.idx_bits();
+        let mut head = Head(self.head.load(Acquire));
+        loop {
+            let idx = head.idx(idx_bits).checked_sub(1)?;
+            let node = &self.nodes[idx];
+            // The node may be popped by others meanwhile, then the cycle of head has changed.
+            let next = match node.next.load(Relaxed) {
+                UNLINKED if idx + 1 < self.nodes.len() => idx + 2,
+                UNLINKED => 0,
+                next => next,
+            };
+            let new = Head::new(next, head.cycle(idx_bits).wrapping_add(1), idx_bits);
+            match self
+                .head
+                .compare_exchange_weak(head.0, new.0, Acquire, Acquire)
+            {
+                Ok(_) => {
+                    return Some(Popped {
+                        node,
+                        idx,
+                        _val: PhantomData,
+                    })
+                }
+                Err(h) => head = Head(h),
+            }
+        }
+    }
+}
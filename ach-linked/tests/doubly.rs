@@ -0,0 +1,143 @@
+use ach_linked::doubly::{LinkedList, Node};
+use core::pin::pin;
+use on_drop::OnDrop;
+use std::thread;
+
+#[test]
+fn base() {
+    let list = LinkedList::new();
+    let mut node1 = pin!(Node::new(1));
+    let mut node2 = pin!(Node::new(2));
+    let mut node3 = pin!(Node::new(3));
+    list.push_back(node2.as_mut());
+    list.push_front(node1.as_mut());
+    list.push_back(node3.as_mut());
+    assert_eq!(list.len(), 3);
+
+    let mut vals = Vec::new();
+    list.for_each(|x| vals.push(*x));
+    assert_eq!(vals, [1, 2, 3]);
+
+    assert!(list.unlink(node2.as_mut()));
+    assert!(!node2.is_linked());
+    assert!(!list.unlink(node2.as_mut()));
+    assert_eq!(list.len(), 2);
+
+    assert_eq!(list.pop_back_with(|x| *x), Some(3));
+    assert!(!node3.is_linked());
+    assert_eq!(list.pop_front_with(|x| *x), Some(1));
+    assert_eq!(list.pop_front_with(|x| *x), None);
+    assert!(list.is_empty());
+}
+
+#[test]
+fn drop_unlink() {
+    let list = LinkedList::new();
+    let mut node1 = Box::pin(Node::new(OnDrop::token(1).0));
+    list.push_back(node1.as_mut());
+
+    let (item, token) = OnDrop::token(2);
+    {
+        let mut node2 = pin!(Node::new(item));
+        list.push_back(node2.as_mut());
+        assert_eq!(list.len(), 2);
+        drop(node1);
+        assert_eq!(list.len(), 1);
+    }
+    assert!(token.is_droped());
+    assert!(list.is_empty());
+}
+
+#[test]
+fn mpmc() {
+    const TEST_TIMES: usize = 1000;
+    static LIST: LinkedList<usize> = LinkedList::new();
+    thread::scope(|s| {
+        for i in 0..TEST_TIMES {
+            s.spawn(move || {
+                let mut node = pin!(Node::new(i));
+                LIST.push_back(node.as_mut());
+                thread::yield_now();
+                if i % 2 == 0 {
+                    LIST.unlink(node.as_mut());
+                }
+            });
+        }
+    });
+    assert!(LIST.is_empty());
+}
+
+#[test]
+fn sorted() {
+    let list = LinkedList::new();
+    let mut node1 = pin!(Node::new((3, 'a')));
+    let mut node2 = pin!(Node::new((1, 'b')));
+    let mut node3 = pin!(Node::new((3, 'c')));
+    let mut node4 = pin!(Node::new((2, 'd')));
+    list.push_sorted_by_key(node1.as_mut(), |x| x.0);
+    list.push_sorted_by_key(node2.as_mut(), |x| x.0);
+    list.push_sorted_by_key(node3.as_mut(), |x| x.0);
+    list.push_sorted_by(node4.as_mut(), |a, b| a.0.cmp(&b.0));
+    assert_eq!(list.front_with(|x| *x), Some((1, 'b')));
+
+    let mut vals = Vec::new();
+    list.for_each(|x| vals.push(x.1));
+    assert_eq!(vals, ['b', 'd', 'a', 'c']);
+
+    assert!(list.unlink(node4.as_mut()));
+    assert_eq!(list.pop_min(), Some((1, 'b')));
+    assert_eq!(list.pop_min_with(|x| x.1), Some('a'));
+    assert_eq!(list.pop_min(), Some((3, 'c')));
+    assert!(list.pop_min().is_none());
+}
+
+#[test]
+fn sorted_long() {
+    const NODE_NUM: usize = 40;
+    let list = LinkedList::new();
+    let mut nodes: Vec<_> = (0..NODE_NUM)
+        .map(|i| Box::pin(Node::new(i * 7 % NODE_NUM)))
+        .collect();
+    for node in nodes.iter_mut() {
+        list.push_sorted_by_key(node.as_mut(), |x| *x);
+    }
+    let mut vals = Vec::new();
+    list.for_each(|x| vals.push(*x));
+    assert_eq!(vals, (0..NODE_NUM).collect::<Vec<_>>());
+
+    let removed = nodes.remove(NODE_NUM / 2);
+    let removed_val = **removed;
+    drop(removed);
+    let mut node = pin!(Node::new(NODE_NUM));
+    list.push_sorted_by_key(node.as_mut(), |x| *x);
+    let mut vals = Vec::new();
+    while let Some(val) = list.pop_min_with(|x| *x) {
+        vals.push(val);
+    }
+    let expected: Vec<_> = (0..=NODE_NUM).filter(|x| *x != removed_val).collect();
+    assert_eq!(vals, expected);
+}
+
+#[test]
+fn sorted_mpmc() {
+    const TEST_TIMES: usize = 1000;
+    static LIST: LinkedList<usize> = LinkedList::new();
+    thread::scope(|s| {
+        for i in 0..TEST_TIMES {
+            s.spawn(move || {
+                let mut node = pin!(Node::new(i));
+                LIST.push_sorted_by_key(node.as_mut(), |x| *x);
+                if i % 3 == 0 {
+                    LIST.unlink(node.as_mut());
+                }
+                let mut last = 0;
+                LIST.for_each(|x| {
+                    assert!(*x >= last);
+                    last = *x;
+                });
+                LIST.pop_min_with(|_| ());
+            });
+        }
+    });
+    assert!(LIST.is_empty());
+}
@@ -0,0 +1,106 @@
+//! Model checked by `RUSTFLAGS="--cfg loom" cargo test --test loom --release`.
+#![cfg(loom)]
+use ach_linked::{LinkedList, Node};
+use loom::sync::Arc;
+use loom::thread;
+
+#[test]
+fn push_take() {
+    loom::model(|| {
+        let list = Arc::new(LinkedList::new());
+        let pusher = {
+            let list = list.clone();
+            thread::spawn(move || {
+                let node = Box::leak(Box::new(Node::new(1)));
+                unsafe { list.push(node) };
+            })
+        };
+        let node = Box::leak(Box::new(Node::new(2)));
+        unsafe { list.push(node) };
+        pusher.join().unwrap();
+
+        let mut vals = Vec::new();
+        for node in list.take_all().unwrap().into_iter() {
+            vals.push(**node);
+            drop(unsafe { Box::from_raw(node) });
+        }
+        vals.sort();
+        assert_eq!(vals, [1, 2]);
+        assert!(list.is_empty());
+    });
+}
+
+#[test]
+fn push_remove() {
+    let mut model = loom::model::Builder::new();
+    model.preemption_bound = Some(2);
+    model.check(|| {
+        let list = Arc::new(LinkedList::new());
+        let mut node = Node::new(1);
+        unsafe { list.push(&mut node) };
+        let remover = {
+            let list = list.clone();
+            thread::spawn(move || {
+                let mut node = Node::new(2);
+                unsafe { list.push(&mut node) };
+                list.remove(&mut node);
+            })
+        };
+        list.remove(&mut node);
+        remover.join().unwrap();
+        assert!(list.is_empty());
+    });
+}
+
+#[test]
+fn stack_pop() {
+    use ach_linked::stack::{Node, Stack};
+    loom::model(|| {
+        let nodes: &'static [Node<usize>] = Box::leak(Box::new([Node::new(1), Node::new(2)]));
+        let stack: &'static Stack<usize> = Box::leak(Box::new(Stack::new(nodes)));
+        let popper = thread::spawn(move || {
+            let node = stack.pop().unwrap();
+            let val = *node;
+            stack.push(node);
+            val
+        });
+        let node = stack.pop().unwrap();
+        let val = *node;
+        stack.push(node);
+        let other = popper.join().unwrap();
+        assert!(val == 1 || val == 2);
+        assert!(other == 1 || other == 2);
+
+        let mut vals = [*stack.pop().unwrap(), *stack.pop().unwrap()];
+        vals.sort();
+        assert_eq!(vals, [1, 2]);
+        assert!(stack.pop().is_none());
+    });
+}
+
+#[test]
+fn stack_aba() {
+    use ach_linked::stack::{Node, Stack};
+    loom::model(|| {
+        let nodes: &'static [Node<usize>] = Box::leak(Box::new([Node::new(1), Node::new(2)]));
+        let stack: &'static Stack<usize> = Box::leak(Box::new(Stack::new(nodes)));
+        // Pops both nodes and pushes the first one back, while the other thread pops.
+        let popper = thread::spawn(move || {
+            let first = stack.pop();
+            let second = stack.pop();
+            if let Some(first) = first {
+                stack.push(first);
+            }
+            second.map(|x| *x)
+        });
+        let node = stack.pop().map(|x| *x);
+        let second = popper.join().unwrap();
+
+        let mut vals: Vec<_> = node.into_iter().chain(second).collect();
+        while let Some(node) = stack.pop() {
+            vals.push(*node);
+        }
+        vals.sort();
+        assert_eq!(vals, [1, 2]);
+    });
+}
@@ -0,0 +1,27 @@
+#![cfg(not(loom))]
+use ach_linked::{LinkedList, Node};
+use std::cell::RefCell;
+use std::thread;
+
+fn assert_send_sync<T: Send + Sync>() {}
+
+#[test]
+fn send_sync() {
+    // `take_all` hands out `&mut Node<T>` from a shared list, so `T: Send` is required.
+    assert_send_sync::<LinkedList<RefCell<u8>>>();
+
+    static LIST: LinkedList<RefCell<u8>> = LinkedList::new();
+    thread::spawn(|| {
+        let node = Box::leak(Box::new(Node::new(RefCell::new(1))));
+        unsafe { LIST.push(node) };
+    })
+    .join()
+    .unwrap();
+    assert_eq!(*LIST.take_all().unwrap().borrow(), 1);
+}
+
+#[test]
+fn compile_fail() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/*.rs");
+}
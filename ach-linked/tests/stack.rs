@@ -0,0 +1,89 @@
+use ach_linked::stack::{Node, Stack};
+use std::collections::BTreeSet;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::thread;
+
+#[test]
+fn base() {
+    let nodes = [Node::new(1), Node::new(2)];
+    let stack = Stack::new(&nodes);
+    assert_eq!(stack.capacity(), 2);
+    assert!(!stack.is_empty());
+
+    let mut node = stack.pop().unwrap();
+    assert_eq!(*node, 1);
+    *node = 3;
+    stack.push(node);
+    assert_eq!(*stack.pop().unwrap(), 3);
+    let node = stack.pop().unwrap();
+    assert_eq!(*node, 2);
+    assert!(stack.pop().is_none());
+    assert!(stack.is_empty());
+    stack.push(node);
+    assert_eq!(*stack.pop().unwrap(), 2);
+
+    let empty = Stack::<u8>::new(&[]);
+    assert!(empty.is_empty());
+    assert!(empty.pop().is_none());
+}
+
+#[test]
+#[should_panic]
+fn push_other() {
+    let nodes1 = [Node::new(1)];
+    let nodes2 = [Node::new(2)];
+    let stack1 = Stack::new(&nodes1);
+    let stack2 = Stack::new(&nodes2);
+    stack2.push(stack1.pop().unwrap());
+}
+
+#[test]
+fn mpmc() {
+    const NODE_NUM: usize = 8;
+    const TEST_TIMES: usize = 10000;
+    let nodes: Vec<_> = (0..NODE_NUM).map(Node::new).collect();
+    let stack = Stack::new(&nodes);
+
+    let owned = Mutex::new(BTreeSet::new());
+    thread::scope(|s| {
+        for _ in 0..4 {
+            s.spawn(|| {
+                for _ in 0..TEST_TIMES {
+                    if let Some(node) = stack.pop() {
+                        assert!(owned.lock().unwrap().insert(*node));
+                        thread::yield_now();
+                        assert!(owned.lock().unwrap().remove(&*node));
+                        stack.push(node);
+                    }
+                }
+            });
+        }
+    });
+
+    let mut all = BTreeSet::new();
+    while let Some(node) = stack.pop() {
+        assert!(all.insert(*node));
+    }
+    assert_eq!(all.len(), NODE_NUM);
+}
+
+#[test]
+fn pop_in_isr() {
+    static NODES: [Node<usize>; 2] = [Node::new(1), Node::new(2)];
+    static STACK: Stack<usize> = Stack::new(&NODES);
+    static POPPED: AtomicBool = AtomicBool::new(false);
+
+    // Holding a popped node doesn't block the pops of others.
+    let node = STACK.pop().unwrap();
+    thread::scope(|s| {
+        s.spawn(|| {
+            assert_eq!(*STACK.pop().unwrap(), 2);
+            POPPED.store(true, Ordering::SeqCst);
+        });
+    });
+    assert!(POPPED.load(Ordering::SeqCst));
+    STACK.push(node);
+    assert_eq!(*STACK.pop().unwrap(), 1);
+    assert!(STACK.pop().is_none());
+}
@@ -0,0 +1,8 @@
+use ach_linked::LinkedList;
+use std::rc::Rc;
+
+fn assert_sync<T: Sync>() {}
+
+fn main() {
+    assert_sync::<LinkedList<Rc<u8>>>();
+}
@@ -1,6 +1,8 @@
 use crate::heapless::Mpmc;
+use ach_ring::Ring;
 use alloc::sync::Arc;
-use util::Error;
+use core::task::Waker;
+use util::{Consumer, Error, Producer, Queue};
 
 #[derive(Clone)]
 pub struct Sender<T, const N: usize> {
@@ -27,3 +29,46 @@ pub fn channel<T, const N: usize>() -> (Sender<T, N>, Receiver<T, N>) {
     let rx = tx.clone();
     (Sender { tx }, Receiver { rx })
 }
+
+impl<T, const N: usize> Producer<T> for Sender<T, N> {
+    fn try_push(&mut self, value: T) -> Result<(), Error<T>> {
+        self.try_send(value)
+    }
+}
+impl<T, const N: usize> Queue<T> for Sender<T, N> {
+    fn capacity(&self) -> usize {
+        N
+    }
+    fn len(&self) -> usize {
+        self.tx.len()
+    }
+    fn is_empty(&self) -> bool {
+        self.tx.is_empty()
+    }
+    fn is_full(&self) -> bool {
+        self.tx.is_full()
+    }
+}
+impl<T, const N: usize> Consumer<T> for Receiver<T, N> {
+    fn try_pop(&mut self) -> Result<T, Error<()>> {
+        self.try_recv()
+    }
+    fn register_waker(&mut self, waker: &Waker) -> bool {
+        Ring::register_waker(&self.rx, waker);
+        true
+    }
+}
+impl<T, const N: usize> Queue<T> for Receiver<T, N> {
+    fn capacity(&self) -> usize {
+        N
+    }
+    fn len(&self) -> usize {
+        self.rx.len()
+    }
+    fn is_empty(&self) -> bool {
+        self.rx.is_empty()
+    }
+    fn is_full(&self) -> bool {
+        self.rx.is_full()
+    }
+}
@@ -1,5 +1,6 @@
 use ach_ring::Ring;
 use core::ops::Deref;
+use core::task::Waker;
 use util::*;
 
 pub struct Sender<'a, T, const N: usize> {
@@ -51,3 +52,88 @@ impl<T, const N: usize> Deref for Mpmc<T, N> {
         &self.ring
     }
 }
+
+impl<'a, T, const N: usize> Producer<T> for Sender<'a, T, N> {
+    fn try_push(&mut self, value: T) -> Result<(), Error<T>> {
+        self.try_send(value)
+    }
+}
+impl<'a, T, const N: usize> Queue<T> for Sender<'a, T, N> {
+    fn capacity(&self) -> usize {
+        N
+    }
+    fn len(&self) -> usize {
+        self.mpmc.len()
+    }
+    fn is_empty(&self) -> bool {
+        self.mpmc.is_empty()
+    }
+    fn is_full(&self) -> bool {
+        self.mpmc.is_full()
+    }
+}
+impl<'a, T, const N: usize> Consumer<T> for Receiver<'a, T, N> {
+    fn try_pop(&mut self) -> Result<T, Error<()>> {
+        self.try_recv()
+    }
+    fn register_waker(&mut self, waker: &Waker) -> bool {
+        Ring::register_waker(self.mpmc, waker);
+        true
+    }
+}
+impl<'a, T, const N: usize> Queue<T> for Receiver<'a, T, N> {
+    fn capacity(&self) -> usize {
+        N
+    }
+    fn len(&self) -> usize {
+        self.mpmc.len()
+    }
+    fn is_empty(&self) -> bool {
+        self.mpmc.is_empty()
+    }
+    fn is_full(&self) -> bool {
+        self.mpmc.is_full()
+    }
+}
+impl<T, const N: usize> Producer<T> for Mpmc<T, N> {
+    fn try_push(&mut self, value: T) -> Result<(), Error<T>> {
+        (&*self).try_push(value)
+    }
+}
+impl<T, const N: usize> Producer<T> for &Mpmc<T, N> {
+    fn try_push(&mut self, value: T) -> Result<(), Error<T>> {
+        self.push(value)
+    }
+}
+impl<T, const N: usize> Consumer<T> for Mpmc<T, N> {
+    fn try_pop(&mut self) -> Result<T, Error<()>> {
+        (&*self).try_pop()
+    }
+    fn register_waker(&mut self, waker: &Waker) -> bool {
+        Ring::register_waker(self, waker);
+        true
+    }
+}
+impl<T, const N: usize> Consumer<T> for &Mpmc<T, N> {
+    fn try_pop(&mut self) -> Result<T, Error<()>> {
+        self.pop()
+    }
+    fn register_waker(&mut self, waker: &Waker) -> bool {
+        Ring::register_waker(self, waker);
+        true
+    }
+}
+impl<T, const N: usize> Queue<T> for Mpmc<T, N> {
+    fn capacity(&self) -> usize {
+        N
+    }
+    fn len(&self) -> usize {
+        self.ring.len()
+    }
+    fn is_empty(&self) -> bool {
+        self.ring.is_empty()
+    }
+    fn is_full(&self) -> bool {
+        self.ring.is_full()
+    }
+}
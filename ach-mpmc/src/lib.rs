@@ -5,3 +5,4 @@ extern crate alloc;
 #[cfg(feature = "alloc")]
 pub mod heap;
 pub mod heapless;
+pub mod priority;
@@ -0,0 +1,133 @@
+use ach_ring::Ring;
+use core::task::Waker;
+use util::*;
+
+/// Mpmc of `K` priorities, each of which has a Ring of `N` values.
+///
+/// Priority 0 is the highest one, values of a higher priority overtake the lower ones.
+pub struct PriorityMpmc<T, const N: usize, const K: usize> {
+    rings: [Ring<T, N>; K],
+}
+impl<T, const N: usize, const K: usize> Default for PriorityMpmc<T, N, K> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl<T, const N: usize, const K: usize> PriorityMpmc<T, N, K> {
+    #[allow(clippy::declare_interior_mutable_const)]
+    const INIT_RING: Ring<T, N> = Ring::new();
+    pub const fn new() -> Self {
+        PriorityMpmc {
+            rings: [Self::INIT_RING; K],
+        }
+    }
+    /// Capacity of all priorities.
+    pub const fn capacity(&self) -> usize {
+        N * K
+    }
+    /// Capacity of each priority.
+    pub const fn capacity_per_priority(&self) -> usize {
+        N
+    }
+    pub const fn priorities(&self) -> usize {
+        K
+    }
+    /// The Ring of `prio`.
+    ///
+    /// Panics if `prio` is out of `0..K`.
+    pub fn ring(&self, prio: usize) -> &Ring<T, N> {
+        &self.rings[prio]
+    }
+    /// Number of values of all priorities.
+    pub fn len(&self) -> usize {
+        self.rings.iter().map(|x| x.len()).sum()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.rings.iter().all(|x| x.is_empty())
+    }
+    /// Whether every priority is full.
+    pub fn is_full(&self) -> bool {
+        self.rings.iter().all(|x| x.is_full())
+    }
+
+    /// Appends a value to the Ring of `prio`.
+    ///
+    /// Returns Err if the Ring is full.
+    ///
+    /// Panics if `prio` is out of `0..K`.
+    pub fn try_send(&self, prio: usize, val: T) -> Result<(), Error<T>> {
+        self.rings[prio].push(val)
+    }
+    /// Registers `waker` to be woken by the next send of any priority.
+    pub fn register_waker(&self, waker: &Waker) {
+        for ring in self.rings.iter() {
+            ring.register_waker(waker);
+        }
+    }
+    /// Appends a value to the Ring of `prio`, waiting while it is full.
+    ///
+    /// Panics if `prio` is out of `0..K`.
+    ///
+    /// Notice: `Spin`
+    pub fn send(&self, prio: usize, val: T) {
+        unwrap(|val| self.try_send(prio, val), val)
+    }
+    /// Removes the first value of the highest non-empty priority.
+    ///
+    /// Returns Err if all of them are empty.
+    pub fn try_recv(&self) -> Result<T, Error<()>> {
+        self.try_recv_prio().map(|(_, val)| val)
+    }
+    /// Removes the first value of the highest non-empty priority, waiting while all of them are empty.
+    ///
+    /// Notice: `Spin`
+    pub fn recv(&self) -> T {
+        unwrap(|_| self.try_recv(), ())
+    }
+    /// Same as `try_recv`, and returns the priority of the value too.
+    pub fn try_recv_prio(&self) -> Result<(usize, T), Error<()>> {
+        for (prio, ring) in self.rings.iter().enumerate() {
+            // Skips the empty ones without popping, so they don't count `Stats::pop_empty`.
+            if ring.is_empty() {
+                continue;
+            }
+            if let Ok(val) = ring.pop() {
+                return Ok((prio, val));
+            }
+        }
+        Err(Error::empty())
+    }
+}
+
+impl<T, const N: usize, const K: usize> Consumer<T> for PriorityMpmc<T, N, K> {
+    fn try_pop(&mut self) -> Result<T, Error<()>> {
+        (&*self).try_pop()
+    }
+    fn register_waker(&mut self, waker: &Waker) -> bool {
+        PriorityMpmc::register_waker(self, waker);
+        true
+    }
+}
+impl<T, const N: usize, const K: usize> Consumer<T> for &PriorityMpmc<T, N, K> {
+    fn try_pop(&mut self) -> Result<T, Error<()>> {
+        self.try_recv()
+    }
+    fn register_waker(&mut self, waker: &Waker) -> bool {
+        PriorityMpmc::register_waker(self, waker);
+        true
+    }
+}
+impl<T, const N: usize, const K: usize> Queue<T> for PriorityMpmc<T, N, K> {
+    fn capacity(&self) -> usize {
+        PriorityMpmc::capacity(self)
+    }
+    fn len(&self) -> usize {
+        PriorityMpmc::len(self)
+    }
+    fn is_empty(&self) -> bool {
+        PriorityMpmc::is_empty(self)
+    }
+    fn is_full(&self) -> bool {
+        PriorityMpmc::is_full(self)
+    }
+}
@@ -0,0 +1,97 @@
+use ach_mpmc::priority::PriorityMpmc;
+use std::thread;
+use util::{ErrorKind, Queue};
+
+#[test]
+fn base() {
+    static MPMC: PriorityMpmc<usize, 2, 3> = PriorityMpmc::new();
+    assert_eq!(MPMC.capacity(), 6);
+    assert_eq!(MPMC.capacity_per_priority(), 2);
+    assert_eq!(MPMC.priorities(), 3);
+    assert!(MPMC.is_empty());
+
+    assert!(MPMC.try_send(2, 20).is_ok());
+    assert!(MPMC.try_send(2, 21).is_ok());
+    assert_eq!(MPMC.try_send(2, 22).unwrap_err().kind, ErrorKind::Full);
+    assert!(MPMC.try_send(1, 10).is_ok());
+    assert!(MPMC.try_send(0, 0).is_ok());
+    assert_eq!(MPMC.len(), 4);
+    assert_eq!(Queue::capacity(&MPMC), 6);
+
+    // The higher priorities overtake.
+    assert_eq!(MPMC.try_recv().unwrap(), 0);
+    assert_eq!(MPMC.try_recv_prio().unwrap(), (1, 10));
+    assert_eq!(MPMC.try_recv().unwrap(), 20);
+    assert!(MPMC.try_send(0, 1).is_ok());
+    assert_eq!(MPMC.try_recv().unwrap(), 1);
+    assert_eq!(MPMC.try_recv().unwrap(), 21);
+    assert_eq!(MPMC.try_recv().unwrap_err().kind, ErrorKind::Empty);
+}
+
+#[test]
+#[should_panic]
+fn out_of_range() {
+    let mpmc: PriorityMpmc<usize, 2, 3> = PriorityMpmc::new();
+    let _ = mpmc.try_send(3, 0);
+}
+
+#[test]
+fn mpmc() {
+    static MPMC: PriorityMpmc<usize, 4, 2> = PriorityMpmc::new();
+    let senders: Vec<_> = (0..2)
+        .map(|prio| {
+            thread::spawn(move || {
+                for i in 0..1000 {
+                    while MPMC.try_send(prio, i).is_err() {
+                        thread::yield_now();
+                    }
+                }
+            })
+        })
+        .collect();
+    let mut last = [None; 2];
+    let mut count = 0;
+    while count < 2000 {
+        if let Ok((prio, val)) = MPMC.try_recv_prio() {
+            // Each priority is still in order.
+            assert!(last[prio].is_none_or(|x| x < val));
+            last[prio] = Some(val);
+            count += 1;
+        } else {
+            thread::yield_now();
+        }
+    }
+    for sender in senders {
+        sender.join().unwrap();
+    }
+    assert!(MPMC.is_empty());
+}
+
+#[test]
+fn blocking() {
+    static MPMC: PriorityMpmc<usize, 1, 2> = PriorityMpmc::new();
+    let sender = thread::spawn(|| {
+        for i in 0..100 {
+            MPMC.send(i % 2, i);
+        }
+    });
+    let mut sum = 0;
+    for _ in 0..100 {
+        sum += MPMC.recv();
+    }
+    sender.join().unwrap();
+    assert_eq!(sum, (0..100).sum());
+    assert!(MPMC.is_empty());
+}
+
+#[cfg(feature = "stats")]
+#[test]
+fn stats() {
+    let mpmc: PriorityMpmc<usize, 2, 3> = PriorityMpmc::new();
+    mpmc.try_send(2, 20).unwrap();
+    assert_eq!(mpmc.try_recv().unwrap(), 20);
+    assert!(mpmc.try_recv().is_err());
+    for prio in 0..3 {
+        assert_eq!(mpmc.ring(prio).stats().pop_empty, 0);
+    }
+}
@@ -0,0 +1,41 @@
+use ach_mpmc::heapless::Mpmc;
+use util::{Consumer, ErrorKind, Producer, Queue};
+
+fn fill<P: Producer<usize> + Queue<usize>>(p: &mut P) {
+    let mut i = 0;
+    while p.try_push(i).is_ok() {
+        i += 1;
+    }
+    assert_eq!(i, p.capacity());
+    assert!(p.is_full());
+}
+fn drain<C: Consumer<usize> + Queue<usize>>(c: &mut C) -> usize {
+    let mut n = 0;
+    while let Ok(x) = c.try_pop() {
+        assert_eq!(x, n);
+        n += 1;
+    }
+    assert_eq!(c.try_pop().unwrap_err().kind, ErrorKind::Empty);
+    assert!(c.is_empty());
+    n
+}
+
+#[test]
+fn heapless() {
+    static MPMC: Mpmc<usize, 3> = Mpmc::new();
+    fill(&mut MPMC.sender());
+    assert_eq!(MPMC.sender().try_push(3).unwrap_err().kind, ErrorKind::Full);
+    assert_eq!(drain(&mut MPMC.recver()), 3);
+
+    let mut shared = &MPMC;
+    fill(&mut MPMC.sender());
+    assert_eq!(drain(&mut shared), 3);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn heap() {
+    let (mut tx, mut rx) = ach_mpmc::heap::channel::<usize, 2>();
+    fill(&mut tx);
+    assert_eq!(drain(&mut rx), 2);
+}
@@ -0,0 +1,44 @@
+use ach_mpmc::heapless::Mpmc;
+use ach_mpmc::priority::PriorityMpmc;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll, Wake, Waker};
+use util::Selector;
+
+struct CountWake(AtomicUsize);
+impl Wake for CountWake {
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref()
+    }
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.0.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+#[test]
+fn future_woken_by_send() {
+    static MPMC: Mpmc<usize, 4> = Mpmc::new();
+    static PRIO: PriorityMpmc<usize, 4, 2> = PriorityMpmc::new();
+    let wake = Arc::new(CountWake(AtomicUsize::new(0)));
+    let waker = Waker::from(wake.clone());
+    let mut cx = Context::from_waker(&waker);
+    let mut rx = MPMC.recver();
+    let mut prio = &PRIO;
+
+    let mut sel = Selector::<usize, 2>::new().with(&mut rx).with(&mut prio);
+    let mut fut = sel.select_async();
+    // Pending without waking itself, the waker is registered on both.
+    assert!(Pin::new(&mut fut).poll(&mut cx).is_pending());
+    assert_eq!(wake.0.load(Ordering::SeqCst), 0);
+    PRIO.try_send(1, 7).unwrap();
+    assert_eq!(wake.0.load(Ordering::SeqCst), 1);
+    assert_eq!(Pin::new(&mut fut).poll(&mut cx), Poll::Ready((1, 7)));
+
+    let mut fut = sel.select_async();
+    assert!(Pin::new(&mut fut).poll(&mut cx).is_pending());
+    MPMC.sender().try_send(8).unwrap();
+    assert_eq!(wake.0.load(Ordering::SeqCst), 2);
+    assert_eq!(Pin::new(&mut fut).poll(&mut cx), Poll::Ready((0, 8)));
+}
@@ -2,34 +2,51 @@
 use core::fmt;
 use core::mem::MaybeUninit;
 use core::ptr;
-use core::sync::atomic::Ordering::SeqCst;
 use interrupt::CriticalSection;
+use util::sync::{Ordering::SeqCst, UnsafeCell};
 use util::*;
 
 pub struct Once<T> {
-    val: MaybeUninit<T>,
+    val: UnsafeCell<MaybeUninit<T>>,
     state: AtomicMemoryState,
 }
+// The value is only written by the owner of the state, and shared after initialized.
+// Sharing it needs `T: Sync`, and it may be set by any thread, which needs `T: Send`.
+unsafe impl<T: Send> Send for Once<T> {}
+unsafe impl<T: Send + Sync> Sync for Once<T> {}
 impl<T> Default for Once<T> {
     fn default() -> Self {
         Self::new()
     }
 }
 impl<T> Once<T> {
-    pub const fn new() -> Self {
-        Once {
-            val: MaybeUninit::uninit(),
-            state: AtomicMemoryState::new(MemoryState::Uninitialized),
+    util::const_fn! {
+        pub fn new() -> Self {
+            Once {
+                val: UnsafeCell::new(MaybeUninit::uninit()),
+                state: AtomicMemoryState::new(MemoryState::Uninitialized),
+            }
         }
     }
-    pub const fn new_with(init: T) -> Self {
-        Once {
-            val: MaybeUninit::new(init),
-            state: AtomicMemoryState::new(MemoryState::Initialized),
+    util::const_fn! {
+        pub fn new_with(init: T) -> Self {
+            Once {
+                val: UnsafeCell::new(MaybeUninit::new(init)),
+                state: AtomicMemoryState::new(MemoryState::Initialized),
+            }
         }
     }
-    fn ptr(&self) -> *mut T {
-        self.val.as_ptr() as *mut T
+    #[inline]
+    unsafe fn read_val(&self) -> T {
+        self.val.with(|p| ptr::read(p).assume_init())
+    }
+    #[inline]
+    unsafe fn write_val(&self, value: T) {
+        self.val.with_mut(|p| ptr::write(p, MaybeUninit::new(value)));
+    }
+    #[inline]
+    unsafe fn val_ref(&self) -> &T {
+        self.val.with(|p| (*p).assume_init_ref())
     }
     pub fn is_initialized(&self) -> bool {
         let state = self.state.load(SeqCst);
@@ -37,7 +54,7 @@ impl<T> Once<T> {
     }
     pub fn take(&mut self) -> Option<T> {
         if self.is_initialized() {
-            let ret = unsafe { ptr::read(self.ptr()) };
+            let ret = unsafe { self.read_val() };
             self.state.store(MemoryState::Uninitialized, SeqCst);
             Some(ret)
         } else {
@@ -46,7 +63,7 @@ impl<T> Once<T> {
     }
     pub fn into_inner(self) -> Option<T> {
         if self.is_initialized() {
-            let ret = unsafe { ptr::read(self.ptr()) };
+            let ret = unsafe { self.read_val() };
             self.state.store(MemoryState::Uninitialized, SeqCst);
             Some(ret)
         } else {
@@ -60,10 +77,11 @@ impl<T> Once<T> {
     pub fn try_get(&self) -> Result<&T, Error<()>> {
         let state = self.state.load(SeqCst);
         if state.is_initialized() {
-            let ret = unsafe { self.val.assume_init_ref() };
+            let ret = unsafe { self.val_ref() };
             Ok(ret)
         } else {
             Err(Error {
+                kind: state.into(),
                 state,
                 input: (),
                 retry: state.is_initializing(),
@@ -76,11 +94,33 @@ impl<T> Once<T> {
     ///
     /// Notice: `Spin`
     pub fn get(&self) -> Result<&T, Error<()>> {
-        retry(|_| self.try_get(), ())
+        match self.try_get() {
+            Err(err) if err.retry => {
+                // Nothing can finish the initialization while the current core is in critical section.
+                debug_assert!(
+                    !interrupt::in_critical_section(),
+                    "Once::get spins, it can't be called in critical section"
+                );
+                retry(|_| self.try_get(), ())
+            }
+            ret => ret,
+        }
+    }
+    /// Same as `get`, but gives up after `attempts` retries.
+    ///
+    /// Returns Err with the input if timeout.
+    pub fn get_for(&self, attempts: usize) -> Result<&T, Error<()>> {
+        retry_with(Bounded::new(Spin, attempts), |_| self.try_get(), ())
+    }
+    /// Same as `get`, but gives up once `clock` reaches `deadline`.
+    ///
+    /// Returns Err with the input if timeout.
+    pub fn get_until<C: Clock>(&self, clock: &C, deadline: C::Instant) -> Result<&T, Error<()>> {
+        retry_with(Deadline::new(clock, deadline), |_| self.try_get(), ())
     }
     pub fn get_mut(&mut self) -> Option<&mut T> {
         if self.is_initialized() {
-            let ret = unsafe { self.val.assume_init_mut() };
+            let ret = unsafe { self.val.with_mut(|p| (*p).assume_init_mut()) };
             Some(ret)
         } else {
             None
@@ -99,12 +139,13 @@ impl<T> Once<T> {
             SeqCst,
         ) {
             Err(Error {
+                kind: state.into(),
                 state,
                 input: value,
                 retry: state.is_erasing(),
             })
         } else {
-            unsafe { ptr::write(self.ptr(), value) };
+            unsafe { self.write_val(value) };
             self.state.store(MemoryState::Initialized, SeqCst);
             Ok(())
         }
@@ -117,33 +158,60 @@ impl<T> Once<T> {
     pub fn set(&self, value: T) -> Result<(), Error<T>> {
         retry(|val| self.try_set(val), value)
     }
+    /// Same as `set`, but gives up after `attempts` retries.
+    ///
+    /// Returns Err with the input if timeout.
+    pub fn set_for(&self, value: T, attempts: usize) -> Result<(), Error<T>> {
+        retry_with(Bounded::new(Spin, attempts), |val| self.try_set(val), value)
+    }
+    /// Same as `set`, but gives up once `clock` reaches `deadline`.
+    ///
+    /// Returns Err with the input if timeout.
+    pub fn set_until<C: Clock>(
+        &self,
+        value: T,
+        clock: &C,
+        deadline: C::Instant,
+    ) -> Result<(), Error<T>> {
+        retry_with(
+            Deadline::new(clock, deadline),
+            |val| self.try_set(val),
+            value,
+        )
+    }
 
     /// Tries to get a reference to the value of the Cell.
     ///
     /// Returns Err if the cell is in critical section.
     pub fn get_or_try_init(&self, value: T) -> Result<&T, Error<T>> {
         let _cs = CriticalSection::new();
-        if let Err(_) = self.state.compare_exchange(
-            MemoryState::Uninitialized,
-            MemoryState::Initializing,
-            SeqCst,
-            SeqCst,
-        ) {
+        if self
+            .state
+            .compare_exchange(
+                MemoryState::Uninitialized,
+                MemoryState::Initializing,
+                SeqCst,
+                SeqCst,
+            )
+            .is_err()
+        {
             self.try_get().map_err(
                 |Error {
+                     kind,
                      state,
                      input: _,
                      retry,
                  }| Error {
+                    kind,
                     state,
                     input: value,
                     retry,
                 },
             )
         } else {
-            unsafe { ptr::write(self.ptr(), value) };
+            unsafe { self.write_val(value) };
             self.state.store(MemoryState::Initialized, SeqCst);
-            Ok(unsafe { self.val.assume_init_ref() })
+            Ok(unsafe { self.val_ref() })
         }
     }
     /// Tries to get a reference to the value of the Cell.
@@ -10,3 +10,27 @@ fn base() {
     assert!(CELL.set(2).is_err());
     assert_eq!(CELL.get().unwrap(), &1);
 }
+
+#[test]
+fn get_in_critical_section() {
+    let cell = Once::new_with(1);
+    let empty: Once<usize> = Once::new();
+    interrupt::free(|_| {
+        assert_eq!(cell.get().unwrap(), &1);
+        assert!(empty.get().is_err());
+        assert_eq!(format!("{:?}", cell), "Ok(1)");
+    });
+}
+
+#[test]
+fn bounded() {
+    use ach_test::Ticks;
+    let clock = Ticks::new();
+    let cell = Once::new();
+
+    assert!(cell.get_for(3).is_err());
+    assert!(cell.set_for(1, 0).is_ok());
+    assert_eq!(cell.set_until(2, &clock, 0).unwrap_err().input, 2);
+    assert_eq!(cell.get_for(0).unwrap(), &1);
+    assert_eq!(cell.get_until(&clock, 0).unwrap(), &1);
+}
@@ -0,0 +1,33 @@
+//! Model checked by `RUSTFLAGS="--cfg loom" cargo test --test loom --release`.
+#![cfg(loom)]
+use ach_once::Once;
+use loom::sync::Arc;
+use loom::thread;
+
+#[test]
+fn set_race() {
+    loom::model(|| {
+        let once = Arc::new(Once::new());
+        let other = {
+            let once = once.clone();
+            thread::spawn(move || once.try_set(1).is_ok())
+        };
+        let mine = once.try_set(2).is_ok();
+        let other = other.join().unwrap();
+        assert!(mine != other);
+        assert_eq!(*once.try_get().unwrap(), if mine { 2 } else { 1 });
+    });
+}
+
+#[test]
+fn get_or_init() {
+    loom::model(|| {
+        let once = Arc::new(Once::new());
+        let other = {
+            let once = once.clone();
+            thread::spawn(move || *once.get_or_init(1))
+        };
+        let mine = *once.get_or_init(2);
+        assert_eq!(other.join().unwrap(), mine);
+    });
+}
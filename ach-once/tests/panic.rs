@@ -0,0 +1,27 @@
+#![cfg(not(loom))]
+use ach_once::Once;
+use ach_test::PanicOnDrop;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+#[test]
+fn drop_after_take() {
+    let mut once = Once::new();
+    once.try_set(PanicOnDrop::new(true)).ok().unwrap();
+    // The value is moved out before dropped, so the once stays consistent.
+    assert!(catch_unwind(AssertUnwindSafe(|| drop(once.take()))).is_err());
+    assert!(!once.is_initialized());
+    assert!(once.try_set(PanicOnDrop::new(false)).is_ok());
+    assert!(once.try_get().is_ok());
+}
+
+#[test]
+fn drop_once() {
+    static DROPS: AtomicUsize = AtomicUsize::new(0);
+    let once = Once::new();
+    once.try_set(PanicOnDrop::counted(true, &DROPS))
+        .ok()
+        .unwrap();
+    assert!(catch_unwind(AssertUnwindSafe(|| drop(once))).is_err());
+    assert_eq!(DROPS.load(Ordering::SeqCst), 1);
+}
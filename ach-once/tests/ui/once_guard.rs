@@ -0,0 +1,9 @@
+use ach_once::Once;
+use std::sync::MutexGuard;
+
+fn assert_sync<T: Sync>() {}
+
+fn main() {
+    // The value may be set by any thread, so it needs `T: Send`.
+    assert_sync::<Once<MutexGuard<'static, u8>>>();
+}
@@ -0,0 +1,8 @@
+use ach_once::Once;
+use std::cell::RefCell;
+
+fn assert_sync<T: Sync>() {}
+
+fn main() {
+    assert_sync::<Once<RefCell<u8>>>();
+}
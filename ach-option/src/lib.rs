@@ -4,32 +4,46 @@ use core::mem::MaybeUninit;
 use core::ptr;
 use core::sync::atomic::Ordering::{Relaxed, SeqCst};
 use interrupt::CriticalSection;
+use util::sync::UnsafeCell;
 use util::*;
 
 pub struct AchOption<T> {
-    val: MaybeUninit<T>,
+    val: UnsafeCell<MaybeUninit<T>>,
     state: AtomicMemoryState,
 }
+// The value is only accessed by the owner of the state, `Debug` doesn't read it.
+// It is moved in and out but never shared, so `T: Send` is enough.
+unsafe impl<T: Send> Send for AchOption<T> {}
+unsafe impl<T: Send> Sync for AchOption<T> {}
 impl<T> Default for AchOption<T> {
     fn default() -> Self {
         Self::new()
     }
 }
 impl<T> AchOption<T> {
-    pub const fn new() -> Self {
-        Self {
-            val: MaybeUninit::uninit(),
-            state: AtomicMemoryState::new(MemoryState::Uninitialized),
+    util::const_fn! {
+        pub fn new() -> Self {
+            Self {
+                val: UnsafeCell::new(MaybeUninit::uninit()),
+                state: AtomicMemoryState::new(MemoryState::Uninitialized),
+            }
         }
     }
-    pub const fn new_with(init: T) -> Self {
-        Self {
-            val: MaybeUninit::new(init),
-            state: AtomicMemoryState::new(MemoryState::Initialized),
+    util::const_fn! {
+        pub fn new_with(init: T) -> Self {
+            Self {
+                val: UnsafeCell::new(MaybeUninit::new(init)),
+                state: AtomicMemoryState::new(MemoryState::Initialized),
+            }
         }
     }
-    fn ptr(&self) -> *mut T {
-        self.val.as_ptr() as *mut T
+    #[inline]
+    unsafe fn read_val(&self) -> T {
+        self.val.with(|p| ptr::read(p).assume_init())
+    }
+    #[inline]
+    unsafe fn write_val(&self, value: T) {
+        self.val.with_mut(|p| ptr::write(p, MaybeUninit::new(value)));
     }
     pub fn into_inner(self) -> Option<T> {
         self.take()
@@ -59,13 +73,14 @@ impl<T> AchOption<T> {
                 Ok(None)
             } else {
                 Err(Error {
+                    kind: state.into(),
                     state,
                     input: (),
                     retry: state.is_transient(),
                 })
             }
         } else {
-            let ret = unsafe { ptr::read(self.ptr()) };
+            let ret = unsafe { self.read_val() };
             self.state.store(MemoryState::Uninitialized, SeqCst);
             Ok(Some(ret))
         }
@@ -76,6 +91,22 @@ impl<T> AchOption<T> {
     pub fn take(&self) -> Option<T> {
         unwrap(|_| self.try_take(), ())
     }
+    /// Same as `take`, but gives up after `attempts` retries.
+    ///
+    /// Returns Err with the input if timeout.
+    pub fn take_for(&self, attempts: usize) -> Result<Option<T>, Error<()>> {
+        retry_with(Bounded::new(Spin, attempts), |_| self.try_take(), ())
+    }
+    /// Same as `take`, but gives up once `clock` reaches `deadline`.
+    ///
+    /// Returns Err with the input if timeout.
+    pub fn take_until<C: Clock>(
+        &self,
+        clock: &C,
+        deadline: C::Instant,
+    ) -> Result<Option<T>, Error<()>> {
+        retry_with(Deadline::new(clock, deadline), |_| self.try_take(), ())
+    }
 
     /// Sets the value of the Option to the argument value.
     ///
@@ -89,12 +120,13 @@ impl<T> AchOption<T> {
             Relaxed,
         ) {
             Err(Error {
+                kind: state.into(),
                 state,
                 input: value,
                 retry: state.is_erasing(),
             })
         } else {
-            unsafe { ptr::write(self.ptr(), value) };
+            unsafe { self.write_val(value) };
             self.state.store(MemoryState::Initialized, SeqCst);
             Ok(())
         }
@@ -106,6 +138,27 @@ impl<T> AchOption<T> {
     pub fn set(&self, value: T) -> Result<(), Error<T>> {
         retry(|val| self.try_set(val), value)
     }
+    /// Same as `set`, but gives up after `attempts` retries.
+    ///
+    /// Returns Err with the input if timeout.
+    pub fn set_for(&self, value: T, attempts: usize) -> Result<(), Error<T>> {
+        retry_with(Bounded::new(Spin, attempts), |val| self.try_set(val), value)
+    }
+    /// Same as `set`, but gives up once `clock` reaches `deadline`.
+    ///
+    /// Returns Err with the input if timeout.
+    pub fn set_until<C: Clock>(
+        &self,
+        value: T,
+        clock: &C,
+        deadline: C::Instant,
+    ) -> Result<(), Error<T>> {
+        retry_with(
+            Deadline::new(clock, deadline),
+            |val| self.try_set(val),
+            value,
+        )
+    }
 
     /// Replaces the contained value with value, and returns the old contained value.
     ///
@@ -123,13 +176,14 @@ impl<T> AchOption<T> {
                 let ret = if state.is_uninitialized() {
                     None
                 } else {
-                    Some(unsafe { ptr::read(self.ptr()) })
+                    Some(unsafe { self.read_val() })
                 };
-                unsafe { ptr::write(self.ptr(), value) };
+                unsafe { self.write_val(value) };
                 self.state.store(MemoryState::Initialized, SeqCst);
                 Ok(ret)
             }
             Err(state) => Err(Error {
+                kind: state.into(),
                 state,
                 input: value,
                 retry: state.is_transient(),
@@ -142,15 +196,43 @@ impl<T> AchOption<T> {
     pub fn replace(&self, value: T) -> Option<T> {
         unwrap(|val| self.try_replace(val), value)
     }
+    /// Same as `replace`, but gives up after `attempts` retries.
+    ///
+    /// Returns Err with the input if timeout.
+    pub fn replace_for(&self, value: T, attempts: usize) -> Result<Option<T>, Error<T>> {
+        retry_with(
+            Bounded::new(Spin, attempts),
+            |val| self.try_replace(val),
+            value,
+        )
+    }
+    /// Same as `replace`, but gives up once `clock` reaches `deadline`.
+    ///
+    /// Returns Err with the input if timeout.
+    pub fn replace_until<C: Clock>(
+        &self,
+        value: T,
+        clock: &C,
+        deadline: C::Instant,
+    ) -> Result<Option<T>, Error<T>> {
+        retry_with(
+            Deadline::new(clock, deadline),
+            |val| self.try_replace(val),
+            value,
+        )
+    }
 }
-impl<T: fmt::Debug> fmt::Debug for AchOption<T> {
+/// Prints by the state alone, the value may be taken by others at any time.
+impl<T> fmt::Debug for AchOption<T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let v = if self.is_some() {
-            Some(unsafe { self.val.assume_init_ref() })
+        let state = self.state.load(SeqCst);
+        if state.is_initialized() {
+            f.write_str("Some(..)")
+        } else if state.is_uninitialized() {
+            f.write_str("None")
         } else {
-            None
-        };
-        fmt::Debug::fmt(&v, f)
+            f.write_str("AchOption { .. }")
+        }
     }
 }
 impl<T> Drop for AchOption<T> {
@@ -14,3 +14,27 @@ fn base() {
     assert_eq!(CELL.replace(3), None);
     assert_eq!(CELL.replace(4), Some(3));
 }
+
+#[test]
+fn bounded() {
+    use ach_test::Ticks;
+    let clock = Ticks::new();
+    let cell = AchOption::new();
+
+    assert!(cell.set_for(1, 0).is_ok());
+    assert_eq!(cell.set_until(2, &clock, 0).unwrap_err().input, 2);
+    assert_eq!(cell.replace_for(3, 0).unwrap(), Some(1));
+    assert_eq!(cell.replace_until(4, &clock, 0).unwrap(), Some(3));
+    assert_eq!(cell.take_for(0).unwrap(), Some(4));
+    assert_eq!(cell.take_until(&clock, 0).unwrap(), None);
+}
+
+#[test]
+fn debug() {
+    let cell = AchOption::new();
+    assert_eq!(format!("{:?}", cell), "None");
+    cell.set(1).unwrap();
+    assert_eq!(format!("{:?}", cell), "Some(..)");
+    assert!(cell.is_some());
+    assert_eq!(cell.take(), Some(1));
+}
@@ -0,0 +1,17 @@
+use ach_option::AchOption;
+use ach_test::PanicOnDrop;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+#[test]
+fn drop_after_take() {
+    let opt = AchOption::new();
+    opt.try_set(PanicOnDrop::new(true)).unwrap();
+    // The value is moved out before dropped, so the option stays consistent.
+    assert!(catch_unwind(AssertUnwindSafe(|| drop(opt.take()))).is_err());
+    assert!(opt.is_none());
+    assert!(opt.try_set(PanicOnDrop::new(false)).is_ok());
+    let old = opt.try_replace(PanicOnDrop::new(true)).ok().unwrap();
+    assert!(old.is_some());
+    assert!(catch_unwind(AssertUnwindSafe(|| drop(opt.try_take()))).is_err());
+    assert!(opt.try_set(PanicOnDrop::new(false)).is_ok());
+}
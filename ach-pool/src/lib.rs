@@ -2,6 +2,7 @@
 
 use ach_option::AchOption;
 use core::ops::Index;
+use util::{Consumer, Error, Producer, Queue};
 
 pub struct Pool<T, const N: usize> {
     buf: [AchOption<T>; N],
@@ -23,6 +24,10 @@ impl<T, const N: usize> Pool<T, N> {
     pub const fn capacity(&self) -> usize {
         Self::CAPACITY
     }
+    /// Number of values, the ones in operation by others are counted if initialized.
+    pub fn len(&self) -> usize {
+        self.buf.iter().filter(|x| x.is_some()).count()
+    }
     pub fn is_empty(&self) -> bool {
         self.buf.iter().all(|x| x.is_none())
     }
@@ -59,3 +64,38 @@ impl<T, const N: usize> Index<usize> for Pool<T, N> {
         &self.buf[index]
     }
 }
+
+impl<T, const N: usize> Producer<T> for Pool<T, N> {
+    fn try_push(&mut self, value: T) -> Result<(), Error<T>> {
+        (&*self).try_push(value)
+    }
+}
+impl<T, const N: usize> Producer<T> for &Pool<T, N> {
+    fn try_push(&mut self, value: T) -> Result<(), Error<T>> {
+        self.push(value).map(|_| ()).map_err(Error::full)
+    }
+}
+impl<T, const N: usize> Consumer<T> for Pool<T, N> {
+    fn try_pop(&mut self) -> Result<T, Error<()>> {
+        (&*self).try_pop()
+    }
+}
+impl<T, const N: usize> Consumer<T> for &Pool<T, N> {
+    fn try_pop(&mut self) -> Result<T, Error<()>> {
+        self.pop().ok_or_else(Error::empty)
+    }
+}
+impl<T, const N: usize> Queue<T> for Pool<T, N> {
+    fn capacity(&self) -> usize {
+        N
+    }
+    fn len(&self) -> usize {
+        Pool::len(self)
+    }
+    fn is_empty(&self) -> bool {
+        Pool::is_empty(self)
+    }
+    fn is_full(&self) -> bool {
+        Pool::is_full(self)
+    }
+}
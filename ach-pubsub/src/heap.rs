@@ -1,8 +1,10 @@
+use crate::send_with;
 use ach_array::Array;
 pub use ach_array::Ref;
 use ach_ring::Ring;
 use alloc::sync::Arc;
-use util::Error;
+use core::task::Waker;
+use util::{Bounded, Clock, Consumer, Deadline, Error, Spin};
 
 pub struct Subscriber<T, const NT: usize, const NS: usize> {
     index: usize,
@@ -18,6 +20,20 @@ impl<T, const NT: usize, const NS: usize> Subscriber<T, NT, NS> {
     pub fn try_recv(&self) -> Result<T, Error<()>> {
         self.ch().pop()
     }
+    /// Snapshot of the counters of the Ring of this subscriber.
+    #[cfg(feature = "stats")]
+    pub fn stats(&self) -> util::Stats {
+        self.ch().stats()
+    }
+}
+impl<T, const NT: usize, const NS: usize> Consumer<T> for Subscriber<T, NT, NS> {
+    fn try_pop(&mut self) -> Result<T, Error<()>> {
+        self.try_recv()
+    }
+    fn register_waker(&mut self, waker: &Waker) -> bool {
+        Ring::register_waker(&self.ch(), waker);
+        true
+    }
 }
 impl<T, const NT: usize, const NS: usize> Drop for Subscriber<T, NT, NS> {
     fn drop(&mut self) {
@@ -54,21 +70,39 @@ impl<T: Clone, const NT: usize, const NS: usize> Publisher<T, NT, NS> {
     ///
     /// Notice: `Spin` if strict
     pub fn send(&self, val: T) -> usize {
-        let mut success: usize = 0;
-        let mut send = None;
-        for sub in self.subscribers.iter(self.strict) {
-            let value = if let Some(v) = send.take() {
-                v
-            } else {
-                val.clone()
-            };
-            if let Err(v) = sub.push(value) {
-                send = Some(v.input);
-            } else {
-                success += 1
-            }
-        }
-        success
+        let sent = if self.strict {
+            send_with(&*self.subscribers, val, Spin, true)
+        } else {
+            // Gives up at once, and skips the subscribers in operation.
+            send_with(&*self.subscribers, val, Bounded::new(Spin, 0), false)
+        };
+        // Neither spinning nor skipping gives up.
+        sent.unwrap_or_else(|err| err.input.1)
+    }
+    /// Same as strict `send`, but gives up after `attempts` retries for each subscriber.
+    ///
+    /// Returns Err with the value and the number of subscribers which received it, if timeout.
+    pub fn send_for(&self, val: T, attempts: usize) -> Result<usize, Error<(T, usize)>> {
+        send_with(&*self.subscribers, val, Bounded::new(Spin, attempts), true)
+    }
+    /// Same as strict `send`, but gives up once `clock` reaches `deadline`.
+    ///
+    /// Returns Err with the value and the number of subscribers which received it, if timeout.
+    pub fn send_until<C: Clock>(
+        &self,
+        val: T,
+        clock: &C,
+        deadline: C::Instant,
+    ) -> Result<usize, Error<(T, usize)>>
+    where
+        C::Instant: Clone,
+    {
+        send_with(
+            &*self.subscribers,
+            val,
+            Deadline::new(clock, deadline),
+            true,
+        )
     }
 }
 impl<T, const NT: usize, const NS: usize> Clone for Publisher<T, NT, NS> {
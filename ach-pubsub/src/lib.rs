@@ -2,6 +2,48 @@
 #[cfg(feature = "alloc")]
 extern crate alloc;
 
+use ach_array::Array;
+use ach_ring::Ring;
+use util::{retry_with, Backoff, Error};
+
 #[cfg(feature = "alloc")]
 pub mod heap;
 pub mod heapless;
+/// Sends to every subscriber, waiting by a clone of `backoff` for each one which is in operation.
+///
+/// If strict, returns Err with the value and the number of subscribers which received it,
+/// if a subscriber isn't ready when `backoff` gives up. Otherwise the subscriber is skipped.
+fn send_with<T: Clone, B: Backoff + Clone, const NT: usize, const NS: usize>(
+    subscribers: &Array<Ring<T, NT>, NS>,
+    val: T,
+    backoff: B,
+    strict: bool,
+) -> Result<usize, Error<(T, usize)>> {
+    let mut success: usize = 0;
+    let mut send = None;
+    for i in 0..subscribers.capacity() {
+        let sub = match retry_with(backoff.clone(), |_| subscribers[i].try_get(), ()) {
+            Ok(sub) => sub,
+            Err(err) if err.retry && strict => {
+                return Err(Error {
+                    kind: err.kind,
+                    state: err.state,
+                    input: (val, success),
+                    retry: true,
+                })
+            }
+            Err(_) => continue,
+        };
+        let value = if let Some(v) = send.take() {
+            v
+        } else {
+            val.clone()
+        };
+        if let Err(v) = sub.push(value) {
+            send = Some(v.input);
+        } else {
+            success += 1
+        }
+    }
+    Ok(success)
+}
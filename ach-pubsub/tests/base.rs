@@ -27,3 +27,48 @@ fn base() {
     assert_eq!(sub1.try_recv().unwrap(), 7);
     assert_eq!(sub3.try_recv().unwrap(), 7);
 }
+
+#[test]
+fn bounded() {
+    use ach_test::Ticks;
+    let clock = Ticks::new();
+    let publisher: Publisher<usize, 1, 3> = Publisher::new(true);
+    let sub1 = publisher.subscribe().unwrap();
+    let sub2 = publisher.subscribe().unwrap();
+
+    assert_eq!(publisher.send_for(1, 0).unwrap(), 2);
+    assert_eq!(publisher.send_until(2, &clock, 0).unwrap(), 0); // full
+    assert_eq!(sub1.try_recv().unwrap(), 1);
+    drop(sub2);
+    assert_eq!(publisher.send_for(3, 0).unwrap(), 1);
+    assert_eq!(sub1.try_recv().unwrap(), 3);
+}
+
+#[test]
+fn bounded_partial() {
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::thread;
+
+    static PUB: Publisher<usize, 4, 2> = Publisher::new(true);
+    static STOP: AtomicBool = AtomicBool::new(false);
+    let sub = PUB.subscribe().unwrap();
+    thread::scope(|s| {
+        // Keeps the second subscriber initializing now and then.
+        s.spawn(|| {
+            while !STOP.load(Ordering::Relaxed) {
+                drop(PUB.subscribe());
+            }
+        });
+        for i in 0..100000 {
+            match PUB.send_for(i, 0) {
+                Ok(n) => assert!(n >= 1),
+                Err(err) => {
+                    // The first subscriber received it before the second one timed out.
+                    assert_eq!(err.input, (i, 1));
+                }
+            }
+            while sub.try_recv().is_ok() {}
+        }
+        STOP.store(true, Ordering::Relaxed);
+    });
+}
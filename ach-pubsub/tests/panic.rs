@@ -0,0 +1,26 @@
+use ach_pubsub::heapless::Publisher;
+use ach_test::PanicOnClone;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static PANIC: AtomicBool = AtomicBool::new(false);
+
+#[test]
+fn clone() {
+    static PUB: Publisher<PanicOnClone<'static>, 3, 2> = Publisher::new(true);
+    let sub1 = PUB.subscribe().unwrap();
+    let sub2 = PUB.subscribe().unwrap();
+
+    PANIC.store(true, Ordering::SeqCst);
+    assert!(catch_unwind(AssertUnwindSafe(|| PUB.send(PanicOnClone::new(1, &PANIC)))).is_err());
+    assert!(sub1.try_recv().is_err());
+
+    // The subscribers are still usable, and can be removed and added again.
+    assert_eq!(PUB.send(PanicOnClone::new(2, &PANIC)), 2);
+    assert_eq!(sub1.try_recv().unwrap(), PanicOnClone::new(2, &PANIC));
+    assert_eq!(sub2.try_recv().unwrap(), PanicOnClone::new(2, &PANIC));
+    drop(sub2);
+    let sub2 = PUB.subscribe().unwrap();
+    assert_eq!(PUB.send_for(PanicOnClone::new(3, &PANIC), 10).unwrap(), 2);
+    assert_eq!(sub2.try_recv().unwrap(), PanicOnClone::new(3, &PANIC));
+}
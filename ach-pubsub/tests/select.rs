@@ -0,0 +1,68 @@
+use ach_pubsub::heapless::Publisher;
+use ach_ring::Ring;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll, Wake, Waker};
+use std::thread;
+use util::Selector;
+
+#[test]
+fn select() {
+    static PUB: Publisher<usize, 4, 1> = Publisher::new(false);
+    static RING: Ring<usize, 4> = Ring::new();
+    let mut sub = PUB.subscribe().unwrap();
+    let mut ring = &RING;
+
+    for i in 0..3 {
+        assert_eq!(PUB.send(i), 1);
+        RING.push(10 + i).unwrap();
+    }
+    let mut sel = Selector::<usize, 2>::new().with(&mut sub).with(&mut ring);
+    let mut got = Vec::new();
+    while let Ok(v) = sel.try_select() {
+        got.push(v);
+    }
+    assert_eq!(got, [(0, 0), (1, 10), (0, 1), (1, 11), (0, 2), (1, 12)]);
+
+    let sender = thread::spawn(|| RING.push(20).unwrap());
+    assert_eq!(sel.select(), (1, 20));
+    sender.join().unwrap();
+}
+
+struct CountWake(AtomicUsize);
+impl Wake for CountWake {
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref()
+    }
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.0.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+#[test]
+fn future_woken_by_push() {
+    static PUB: Publisher<usize, 4, 1> = Publisher::new(false);
+    static RING: Ring<usize, 4> = Ring::new();
+    let wake = Arc::new(CountWake(AtomicUsize::new(0)));
+    let waker = Waker::from(wake.clone());
+    let mut cx = Context::from_waker(&waker);
+    let mut sub = PUB.subscribe().unwrap();
+    let mut ring = &RING;
+
+    let mut sel = Selector::<usize, 2>::new().with(&mut sub).with(&mut ring);
+    let mut fut = sel.select_async();
+    // Pending without waking itself, the waker is registered on both.
+    assert!(Pin::new(&mut fut).poll(&mut cx).is_pending());
+    assert_eq!(wake.0.load(Ordering::SeqCst), 0);
+    assert_eq!(PUB.send(1), 1);
+    assert_eq!(wake.0.load(Ordering::SeqCst), 1);
+    assert_eq!(Pin::new(&mut fut).poll(&mut cx), Poll::Ready((0, 1)));
+
+    let mut fut = sel.select_async();
+    assert!(Pin::new(&mut fut).poll(&mut cx).is_pending());
+    thread::spawn(|| RING.push(2).unwrap()).join().unwrap();
+    assert_eq!(wake.0.load(Ordering::SeqCst), 2);
+    assert_eq!(Pin::new(&mut fut).poll(&mut cx), Poll::Ready((1, 2)));
+}
@@ -0,0 +1,26 @@
+#![cfg(feature = "stats")]
+use ach_pubsub::heapless::Publisher;
+
+#[test]
+fn base() {
+    static PUB: Publisher<usize, 2, 2> = Publisher::new(false);
+    let sub1 = PUB.subscribe().unwrap();
+    let sub2 = PUB.subscribe().unwrap();
+    assert_eq!(PUB.send(1), 2);
+    assert_eq!(sub2.try_recv().unwrap(), 1);
+    assert_eq!(PUB.send(2), 2);
+    // `sub1` is full.
+    assert_eq!(PUB.send(3), 1);
+    assert!(sub2.try_recv().is_ok());
+    assert!(sub2.try_recv().is_ok());
+    assert!(sub2.try_recv().is_err());
+
+    let stats1 = sub1.stats();
+    assert_eq!(stats1.max_len, 2);
+    assert_eq!(stats1.push_failures, 1);
+    assert_eq!(stats1.pop_empty, 0);
+    let stats2 = sub2.stats();
+    assert_eq!(stats2.max_len, 2);
+    assert_eq!(stats2.push_failures, 0);
+    assert_eq!(stats2.pop_empty, 1);
+}
@@ -91,5 +91,67 @@ pub fn mpsc(c: &mut Criterion) {
     });
 }
 
-criterion_group!(benches, mpsc);
+fn ring_spsc<const N: usize>(c: &mut Criterion, name: &str, ring: &'static Ring<u32, N>) {
+    c.bench_function(name, |b| {
+        b.iter_custom(|iters| {
+            let start = Instant::now();
+
+            crossbeam_utils::thread::scope(|scope| {
+                let msgs = iters as usize;
+
+                scope.spawn(move |_| {
+                    for _ in 0..msgs {
+                        while ring.push(Default::default()).is_err() {
+                            thread::yield_now();
+                        }
+                    }
+                });
+
+                for _ in 0..msgs {
+                    while ring.pop().is_err() {}
+                }
+            })
+            .unwrap();
+
+            start.elapsed()
+        });
+    });
+}
+
+/// The capacity of power of two uses mask arithmetic, build with `--features cache-padded` to pad the indexes.
+pub fn spsc(c: &mut Criterion) {
+    const CAPACITY: usize = 64;
+    static POW2: Ring<u32, CAPACITY> = Ring::new();
+    static NOT_POW2: Ring<u32, { CAPACITY - 1 }> = Ring::new();
+    ring_spsc(c, "ring::spsc::pow2", &POW2);
+    ring_spsc(c, "ring::spsc::not_pow2", &NOT_POW2);
+    c.bench_function("crossbeam::spsc", |b| {
+        b.iter_custom(|iters| {
+            let queue = Arc::new(ArrayQueue::<u32>::new(CAPACITY));
+            let start = Instant::now();
+
+            crossbeam_utils::thread::scope(|scope| {
+                let msgs = iters as usize;
+
+                let tx = queue.clone();
+                scope.spawn(move |_| {
+                    for _ in 0..msgs {
+                        while tx.push(Default::default()).is_err() {
+                            thread::yield_now();
+                        }
+                    }
+                });
+
+                for _ in 0..msgs {
+                    while queue.pop().is_none() {}
+                }
+            })
+            .unwrap();
+
+            start.elapsed()
+        });
+    });
+}
+
+criterion_group!(benches, mpsc, spsc);
 criterion_main!(benches);
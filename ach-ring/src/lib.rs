@@ -1,17 +1,28 @@
 #![no_std]
 
+#[cfg(loom)]
+use core::array;
 use core::mem::MaybeUninit;
-use core::sync::atomic::{AtomicUsize, Ordering};
-use core::{ptr, slice};
+use core::ptr;
+#[cfg(not(loom))]
+use core::slice;
+use core::task::Waker;
+use util::sync::{AtomicUsize, Ordering, UnsafeCell};
 use util::*;
 
 pub struct Ring<T, const N: usize> {
-    buf: MaybeUninit<[T; N]>,
+    buf: [UnsafeCell<MaybeUninit<T>>; N],
     /// always points to the first element
-    start: AtomicUsize,
-    end: AtomicUsize,
+    start: CachePadded<AtomicUsize>,
+    end: CachePadded<AtomicUsize>,
     pub ops: [AtomicMemoryRing; N],
+    waker: AtomicWaker,
+    stats: Counters,
 }
+// The slots are only accessed by the owner of their state.
+// Values are moved in and out but never shared, so `T: Send` is enough.
+unsafe impl<T: Send, const N: usize> Send for Ring<T, N> {}
+unsafe impl<T: Send, const N: usize> Sync for Ring<T, N> {}
 impl<T, const N: usize> Default for Ring<T, N> {
     fn default() -> Self {
         Self::new()
@@ -20,16 +31,56 @@ impl<T, const N: usize> Default for Ring<T, N> {
 impl<T, const N: usize> Ring<T, N> {
     const CAPACITY: usize = N;
     const WRAP_MAX: usize = MemoryRing::max_idx(Self::CAPACITY);
+    /// With `N` of power of two, the indexes wrap at `usize::MAX` by themselves,
+    /// as long as every cycle fits in the index.
+    const WRAP_NATURAL: bool = N.is_power_of_two() && N - 1 <= usize::MAX / MemoryRing::max_cycle();
+    #[cfg(not(loom))]
+    #[allow(clippy::declare_interior_mutable_const)]
+    const INIT_BUF: UnsafeCell<MaybeUninit<T>> = UnsafeCell::new(MaybeUninit::uninit());
+    #[cfg(not(loom))]
     #[allow(clippy::declare_interior_mutable_const)]
     const INIT_STATE: AtomicMemoryRing = AtomicMemoryRing::new(MemoryRing::INIT);
+    #[cfg(not(loom))]
     pub const fn new() -> Self {
         Ring {
-            buf: MaybeUninit::uninit(),
-            start: AtomicUsize::new(0),
-            end: AtomicUsize::new(0),
+            buf: [Self::INIT_BUF; N],
+            start: CachePadded::new(AtomicUsize::new(0)),
+            end: CachePadded::new(AtomicUsize::new(0)),
             ops: [Self::INIT_STATE; N],
+            waker: AtomicWaker::new(),
+            stats: Counters::new(),
         }
     }
+    #[cfg(loom)]
+    pub fn new() -> Self {
+        Ring {
+            buf: array::from_fn(|_| UnsafeCell::new(MaybeUninit::uninit())),
+            start: CachePadded::new(AtomicUsize::new(0)),
+            end: CachePadded::new(AtomicUsize::new(0)),
+            ops: array::from_fn(|_| AtomicMemoryRing::new(MemoryRing::INIT)),
+            waker: AtomicWaker::new(),
+            stats: Counters::new(),
+        }
+    }
+    /// Same as `new`, but the indexes start at `idx`, to test the wrapping of them.
+    #[doc(hidden)]
+    pub fn with_index(idx: usize) -> Self {
+        debug_assert!(Self::WRAP_NATURAL || idx < Self::WRAP_MAX);
+        let ring = Self::new();
+        ring.start.store(idx, Ordering::Relaxed);
+        ring.end.store(idx, Ordering::Relaxed);
+        for (i, op) in ring.ops.iter().enumerate() {
+            // The first index at or after `idx` which uses the slot.
+            let next = idx.wrapping_add((i + N - ring.index(idx)) % N);
+            let cycle = MemoryRing::cycle_of_idx(next, Self::CAPACITY);
+            op.store(
+                MemoryRing::new(cycle, MemoryState::Uninitialized),
+                Ordering::Relaxed,
+            );
+        }
+        ring
+    }
+    #[cfg(not(loom))]
     fn ptr(&self) -> *mut T {
         self.buf.as_ptr() as *mut T
     }
@@ -38,7 +89,9 @@ impl<T, const N: usize> Ring<T, N> {
     }
 
     fn wrap_len(&self, start: usize, end: usize) -> usize {
-        if end >= start {
+        if Self::WRAP_NATURAL {
+            end.wrapping_sub(start)
+        } else if end >= start {
             end - start
         } else {
             Self::WRAP_MAX - start + end
@@ -55,21 +108,42 @@ impl<T, const N: usize> Ring<T, N> {
     pub fn is_full(&self) -> bool {
         self.len() >= Self::CAPACITY
     }
+    /// Snapshot of the counters of max len, failures and retries.
+    #[cfg(feature = "stats")]
+    pub fn stats(&self) -> Stats {
+        self.stats.snapshot()
+    }
+    #[cfg(feature = "stats")]
+    pub fn reset_stats(&self) {
+        self.stats.reset()
+    }
+    /// Registers `waker` to be woken by the next push.
+    ///
+    /// Only one waker is kept, the one of another task is woken when replaced.
+    pub fn register_waker(&self, waker: &Waker) {
+        self.waker.register(waker)
+    }
     #[inline]
     unsafe fn buffer_read(&self, off: usize) -> T {
-        ptr::read(self.ptr().add(off))
+        self.buf[off].with(|p| ptr::read(p).assume_init())
     }
     #[inline]
     unsafe fn buffer_write(&self, off: usize, value: T) {
-        ptr::write(self.ptr().add(off), value);
+        self.buf[off].with_mut(|p| ptr::write(p, MaybeUninit::new(value)));
     }
     #[inline]
     fn index(&self, idx: usize) -> usize {
-        idx % Self::CAPACITY
+        if Self::WRAP_NATURAL {
+            idx & (Self::CAPACITY - 1)
+        } else {
+            idx % Self::CAPACITY
+        }
     }
     #[inline]
     fn next_idx(&self, old: usize) -> usize {
-        if old == Self::WRAP_MAX - 1 {
+        if Self::WRAP_NATURAL {
+            old.wrapping_add(1)
+        } else if old == Self::WRAP_MAX - 1 {
             0
         } else {
             old + 1
@@ -85,6 +159,7 @@ impl<T, const N: usize> Ring<T, N> {
         self.start
             .compare_exchange_weak(old, new, Ordering::SeqCst, Ordering::Relaxed)
     }
+    #[cfg(not(loom))]
     pub fn as_mut_slices(&mut self) -> (&mut [T], &mut [T]) {
         let ptr = self.ptr();
         let start = self.start.load(Ordering::Relaxed);
@@ -106,13 +181,24 @@ impl<T, const N: usize> Ring<T, N> {
             )
         }
     }
+    #[cfg(not(loom))]
     pub fn clear(&mut self) {
         let (a, b) = self.as_mut_slices();
-        unsafe { ptr::drop_in_place(a) };
-        unsafe { ptr::drop_in_place(b) };
+        let (a, b) = (a as *mut [T], b as *mut [T]);
+        // Empty before dropping, so the Ring stays consistent if a `drop` panics.
         self.end.store(0, Ordering::Relaxed);
         self.start.store(0, Ordering::Relaxed);
         self.ops = [Self::INIT_STATE; N];
+        let _b = defer(|| unsafe { ptr::drop_in_place(b) });
+        unsafe { ptr::drop_in_place(a) };
+    }
+    /// The buffer of loom isn't contiguous, so pops every element.
+    #[cfg(loom)]
+    pub fn clear(&mut self) {
+        while self.pop().is_ok() {}
+        self.end.store(0, Ordering::Relaxed);
+        self.start.store(0, Ordering::Relaxed);
+        self.ops = array::from_fn(|_| AtomicMemoryRing::new(MemoryRing::INIT));
     }
 
     /// Removes the first element and returns it.
@@ -130,6 +216,7 @@ impl<T, const N: usize> Ring<T, N> {
             let state = op.state();
             if op >= expect {
                 if let Err(i) = self.add_ptr_start(start) {
+                    self.stats.cas_retry();
                     start = i;
                     continue;
                 } else {
@@ -139,7 +226,9 @@ impl<T, const N: usize> Ring<T, N> {
                     return Ok(ret);
                 }
             } else {
+                self.stats.pop_empty();
                 return Err(Error {
+                    kind: ErrorKind::Empty,
                     state,
                     input: (),
                     retry: false,
@@ -163,16 +252,21 @@ impl<T, const N: usize> Ring<T, N> {
             let state = op.state();
             if op >= expect {
                 if let Err(i) = self.add_ptr_end(end) {
+                    self.stats.cas_retry();
                     end = i;
                     continue;
                 } else {
                     unsafe { self.buffer_write(index, value) };
                     let op = MemoryRing::new(cycle, MemoryState::Initialized);
                     self.ops[index].store(op, Ordering::Release);
+                    self.stats.observe_len(|| self.len());
+                    self.waker.wake();
                     return Ok(());
                 }
             } else {
+                self.stats.push_failed();
                 return Err(Error {
+                    kind: ErrorKind::Full,
                     state,
                     input: value,
                     retry: false,
@@ -186,3 +280,46 @@ impl<T, const N: usize> Drop for Ring<T, N> {
         self.clear()
     }
 }
+
+impl<T, const N: usize> Producer<T> for Ring<T, N> {
+    fn try_push(&mut self, value: T) -> Result<(), Error<T>> {
+        (&*self).try_push(value)
+    }
+}
+impl<T, const N: usize> Producer<T> for &Ring<T, N> {
+    fn try_push(&mut self, value: T) -> Result<(), Error<T>> {
+        self.push(value)
+    }
+}
+impl<T, const N: usize> Consumer<T> for Ring<T, N> {
+    fn try_pop(&mut self) -> Result<T, Error<()>> {
+        (&*self).try_pop()
+    }
+    fn register_waker(&mut self, waker: &Waker) -> bool {
+        Ring::register_waker(self, waker);
+        true
+    }
+}
+impl<T, const N: usize> Consumer<T> for &Ring<T, N> {
+    fn try_pop(&mut self) -> Result<T, Error<()>> {
+        self.pop()
+    }
+    fn register_waker(&mut self, waker: &Waker) -> bool {
+        Ring::register_waker(self, waker);
+        true
+    }
+}
+impl<T, const N: usize> Queue<T> for Ring<T, N> {
+    fn capacity(&self) -> usize {
+        N
+    }
+    fn len(&self) -> usize {
+        Ring::len(self)
+    }
+    fn is_empty(&self) -> bool {
+        Ring::is_empty(self)
+    }
+    fn is_full(&self) -> bool {
+        Ring::is_full(self)
+    }
+}
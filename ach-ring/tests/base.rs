@@ -1,4 +1,6 @@
 use ach_ring::Ring;
+use core::sync::atomic::Ordering;
+use util::{ErrorKind, MemoryRing, MemoryState};
 
 #[test]
 fn base() {
@@ -10,12 +12,72 @@ fn base() {
     assert!(!VEC.is_empty());
     assert!(VEC.push(2).is_ok());
     assert!(VEC.push(3).is_ok());
-    assert!(VEC.push(4).is_err());
+    assert_eq!(VEC.push(4).unwrap_err().kind, ErrorKind::Full);
     assert_eq!(VEC.pop().unwrap(), 1);
     assert!(VEC.push(5).is_ok());
     assert_eq!(VEC.pop().unwrap(), 2);
     assert_eq!(VEC.pop().unwrap(), 3);
     assert_eq!(VEC.pop().unwrap(), 5);
-    assert!(VEC.pop().is_err());
+    assert_eq!(VEC.pop().unwrap_err().kind, ErrorKind::Empty);
     assert!(VEC.push(6).is_ok());
 }
+
+#[test]
+fn pow2() {
+    let ring: Ring<usize, 4> = Ring::new();
+    for round in 0..3 {
+        for i in 0..4 {
+            assert!(ring.push(round * 4 + i).is_ok());
+        }
+        assert_eq!(ring.push(0).unwrap_err().kind, ErrorKind::Full);
+        assert_eq!(ring.len(), 4);
+        for i in 0..3 {
+            assert_eq!(ring.pop().unwrap(), round * 4 + i);
+        }
+        assert_eq!(ring.len(), 1);
+        assert_eq!(ring.pop().unwrap(), round * 4 + 3);
+        assert!(ring.is_empty());
+    }
+    assert_eq!(
+        ring.ops[0].load(Ordering::Relaxed),
+        MemoryRing::new(3, MemoryState::Uninitialized)
+    );
+}
+
+#[test]
+fn wrap_natural() {
+    // The indexes cross `usize::MAX` in the second round.
+    let ring: Ring<usize, 4> = Ring::with_index(usize::MAX - 5);
+    for round in 0..4 {
+        for i in 0..4 {
+            assert!(ring.push(round * 4 + i).is_ok());
+            assert_eq!(ring.len(), i + 1);
+        }
+        assert_eq!(ring.push(0).unwrap_err().kind, ErrorKind::Full);
+        for i in 0..4 {
+            assert_eq!(ring.pop().unwrap(), round * 4 + i);
+        }
+        assert_eq!(ring.pop().unwrap_err().kind, ErrorKind::Empty);
+    }
+}
+
+#[test]
+fn cycle_wrap() {
+    let cycles = MemoryRing::max_cycle();
+    if cycles > 1 << 24 {
+        // Too many cycles to run through with `wide-state`.
+        return;
+    }
+    let ring: Ring<usize, 1> = Ring::new();
+    for i in 0..cycles + 2 {
+        ring.push(i).unwrap();
+        assert_eq!(ring.pop().unwrap(), i);
+    }
+    assert!(ring.pop().is_err());
+    ring.push(0).unwrap();
+    assert!(ring.push(1).is_err());
+    assert_eq!(
+        ring.ops[0].load(Ordering::Relaxed),
+        MemoryRing::new(2, MemoryState::Initialized)
+    );
+}
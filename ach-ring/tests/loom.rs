@@ -0,0 +1,81 @@
+//! Model checked by `RUSTFLAGS="--cfg loom" cargo test --test loom --release`.
+#![cfg(loom)]
+use ach_ring::Ring;
+use loom::sync::Arc;
+use loom::thread;
+
+fn pop<const N: usize>(ring: &Ring<usize, N>) -> usize {
+    loop {
+        match ring.pop() {
+            Ok(v) => return v,
+            Err(_) => thread::yield_now(),
+        }
+    }
+}
+
+#[test]
+fn mpsc() {
+    let mut model = loom::model::Builder::new();
+    model.preemption_bound = Some(3);
+    model.check(|| {
+        let ring = Arc::new(Ring::<usize, 2>::new());
+        let producers: Vec<_> = (0..2)
+            .map(|i| {
+                let ring = ring.clone();
+                thread::spawn(move || ring.push(i).unwrap())
+            })
+            .collect();
+        let a = pop(&ring);
+        let b = pop(&ring);
+        for h in producers {
+            h.join().unwrap();
+        }
+        assert_eq!(a + b, 1);
+        assert!(ring.is_empty());
+    });
+}
+
+#[test]
+fn wrap() {
+    loom::model(|| {
+        let ring = Arc::new(Ring::<usize, 1>::new());
+        let producer = {
+            let ring = ring.clone();
+            thread::spawn(move || {
+                for i in 0..2 {
+                    while ring.push(i).is_err() {
+                        thread::yield_now();
+                    }
+                }
+            })
+        };
+        assert_eq!(pop(&ring), 0);
+        assert_eq!(pop(&ring), 1);
+        producer.join().unwrap();
+    });
+}
+
+#[test]
+fn waker() {
+    use loom::sync::atomic::{AtomicBool, Ordering};
+    use std::task::{Wake, Waker};
+    struct Flag(AtomicBool);
+    impl Wake for Flag {
+        fn wake(self: std::sync::Arc<Self>) {
+            self.0.store(true, Ordering::SeqCst);
+        }
+    }
+    loom::model(|| {
+        let ring = Arc::new(Ring::<usize, 1>::new());
+        let flag = std::sync::Arc::new(Flag(AtomicBool::new(false)));
+        let producer = {
+            let ring = ring.clone();
+            thread::spawn(move || ring.push(1).unwrap())
+        };
+        // The push is either seen after registering, or wakes the waker.
+        ring.register_waker(&Waker::from(flag.clone()));
+        let popped = ring.pop().is_ok();
+        producer.join().unwrap();
+        assert!(popped || flag.0.load(Ordering::SeqCst));
+    });
+}
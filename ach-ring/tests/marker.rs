@@ -0,0 +1,24 @@
+#![cfg(not(loom))]
+use ach_ring::Ring;
+use std::cell::RefCell;
+use std::thread;
+
+fn assert_send_sync<T: Send + Sync>() {}
+
+#[test]
+fn send_sync() {
+    // The values are only moved, so `T: Send` is enough to share the ring.
+    assert_send_sync::<Ring<RefCell<u8>, 4>>();
+
+    static RING: Ring<RefCell<u8>, 4> = Ring::new();
+    thread::spawn(|| RING.push(RefCell::new(1)).unwrap())
+        .join()
+        .unwrap();
+    assert_eq!(RING.pop().unwrap().into_inner(), 1);
+}
+
+#[test]
+fn compile_fail() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/*.rs");
+}
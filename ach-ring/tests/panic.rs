@@ -0,0 +1,31 @@
+#![cfg(not(loom))]
+use ach_ring::Ring;
+use ach_test::PanicOnDrop;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static DROPS: AtomicUsize = AtomicUsize::new(0);
+
+#[test]
+fn clear() {
+    let mut ring: Ring<PanicOnDrop<'static>, 4> = Ring::new();
+    // Wraps, so the values are in two slices.
+    for _ in 0..3 {
+        ring.push(PanicOnDrop::counted(false, &DROPS)).unwrap();
+        drop(ring.pop().unwrap());
+    }
+    ring.push(PanicOnDrop::counted(true, &DROPS)).unwrap();
+    for _ in 0..3 {
+        ring.push(PanicOnDrop::counted(false, &DROPS)).unwrap();
+    }
+    DROPS.store(0, Ordering::SeqCst);
+    assert!(catch_unwind(AssertUnwindSafe(|| ring.clear())).is_err());
+    // Every value is dropped once, and the Ring is empty and usable.
+    assert_eq!(DROPS.load(Ordering::SeqCst), 4);
+    assert!(ring.is_empty());
+    assert!(ring.pop().is_err());
+    ring.push(PanicOnDrop::counted(false, &DROPS)).unwrap();
+    assert_eq!(ring.len(), 1);
+    drop(ring);
+    assert_eq!(DROPS.load(Ordering::SeqCst), 5);
+}
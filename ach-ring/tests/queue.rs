@@ -0,0 +1,38 @@
+use ach_ring::Ring;
+use util::{Consumer, ErrorKind, Producer, Queue};
+
+fn fill<Q: Producer<usize> + Queue<usize>>(q: &mut Q) {
+    let mut i = 0;
+    while q.try_push(i).is_ok() {
+        i += 1;
+    }
+    assert_eq!(i, q.capacity());
+    assert!(q.is_full());
+}
+fn drain<Q: Consumer<usize>>(q: &mut Q) -> usize {
+    let mut n = 0;
+    while let Ok(x) = q.try_pop() {
+        assert_eq!(x, n);
+        n += 1;
+    }
+    assert_eq!(q.try_pop().unwrap_err().kind, ErrorKind::Empty);
+    n
+}
+
+#[test]
+fn owned() {
+    let mut ring: Ring<usize, 3> = Ring::new();
+    fill(&mut ring);
+    assert_eq!(ring.try_push(3).unwrap_err().kind, ErrorKind::Full);
+    assert_eq!(drain(&mut ring), 3);
+    assert!(Queue::is_empty(&ring));
+}
+
+#[test]
+fn shared() {
+    static RING: Ring<usize, 4> = Ring::new();
+    let mut producer = &RING;
+    let mut consumer = &RING;
+    fill(&mut producer);
+    assert_eq!(drain(&mut consumer), 4);
+}
@@ -0,0 +1,53 @@
+#![cfg(feature = "stats")]
+use ach_ring::Ring;
+use std::thread;
+use util::Stats;
+
+#[test]
+fn base() {
+    let ring: Ring<usize, 3> = Ring::new();
+    assert_eq!(ring.stats(), Stats::default());
+    assert!(ring.pop().is_err());
+    for i in 0..3 {
+        ring.push(i).unwrap();
+    }
+    assert!(ring.push(3).is_err());
+    assert!(ring.push(4).is_err());
+    assert_eq!(ring.pop().unwrap(), 0);
+    ring.push(5).unwrap();
+    let stats = ring.stats();
+    assert_eq!(stats.max_len, 3);
+    assert_eq!(stats.push_failures, 2);
+    assert_eq!(stats.pop_empty, 1);
+    assert_eq!(stats.cas_retries, 0);
+
+    ring.reset_stats();
+    assert_eq!(ring.stats(), Stats::default());
+}
+
+#[test]
+fn mpmc() {
+    static RING: Ring<usize, 8> = Ring::new();
+    let pushers: Vec<_> = (0..4)
+        .map(|_| {
+            thread::spawn(|| {
+                for i in 0..500 {
+                    while RING.push(i).is_err() {
+                        thread::yield_now();
+                    }
+                }
+            })
+        })
+        .collect();
+    let mut count = 0;
+    while count < 2000 {
+        if RING.pop().is_ok() {
+            count += 1;
+        }
+    }
+    for pusher in pushers {
+        pusher.join().unwrap();
+    }
+    let stats = RING.stats();
+    assert!(stats.max_len > 0 && stats.max_len <= 8);
+}
@@ -0,0 +1,8 @@
+use ach_ring::Ring;
+use std::rc::Rc;
+
+fn assert_sync<T: Sync>() {}
+
+fn main() {
+    assert_sync::<Ring<Rc<u8>, 4>>();
+}
@@ -1,26 +1,81 @@
 use crate::heapless::Spsc;
 use alloc::sync::Arc;
+use core::mem;
+use util::{Consumer, Error, Producer, Queue};
 
+/// Holds the sender end of the Spsc until dropped.
 pub struct Sender<T, const N: usize> {
     tx: Arc<Spsc<T, N>>,
 }
 impl<T, const N: usize> Sender<T, N> {
     pub fn try_send(&mut self, val: T) -> Result<(), T> {
-        self.tx.take_sender().unwrap().try_send(val)
+        self.tx.push(val)
+    }
+}
+impl<T, const N: usize> Drop for Sender<T, N> {
+    fn drop(&mut self) {
+        unsafe { self.tx.free_sender() };
     }
 }
 
+/// Holds the receiver end of the Spsc until dropped.
 pub struct Receiver<T, const N: usize> {
     rx: Arc<Spsc<T, N>>,
 }
 impl<T, const N: usize> Receiver<T, N> {
     pub fn try_recv(&mut self) -> Option<T> {
-        self.rx.take_recver().unwrap().try_recv()
+        self.rx.pop()
+    }
+}
+impl<T, const N: usize> Drop for Receiver<T, N> {
+    fn drop(&mut self) {
+        unsafe { self.rx.free_recver() };
     }
 }
 
 pub fn channel<T, const N: usize>() -> (Sender<T, N>, Receiver<T, N>) {
     let tx = Arc::new(Spsc::new());
+    // The ends are held by the handles, instead of the borrowing ones.
+    mem::forget(tx.split().unwrap());
     let rx = tx.clone();
     (Sender { tx }, Receiver { rx })
 }
+
+impl<T, const N: usize> Producer<T> for Sender<T, N> {
+    fn try_push(&mut self, value: T) -> Result<(), Error<T>> {
+        self.try_send(value).map_err(Error::full)
+    }
+}
+impl<T, const N: usize> Queue<T> for Sender<T, N> {
+    fn capacity(&self) -> usize {
+        N
+    }
+    fn len(&self) -> usize {
+        self.tx.len()
+    }
+    fn is_empty(&self) -> bool {
+        self.tx.is_empty()
+    }
+    fn is_full(&self) -> bool {
+        self.tx.is_full()
+    }
+}
+impl<T, const N: usize> Consumer<T> for Receiver<T, N> {
+    fn try_pop(&mut self) -> Result<T, Error<()>> {
+        self.try_recv().ok_or_else(Error::empty)
+    }
+}
+impl<T, const N: usize> Queue<T> for Receiver<T, N> {
+    fn capacity(&self) -> usize {
+        N
+    }
+    fn len(&self) -> usize {
+        self.rx.len()
+    }
+    fn is_empty(&self) -> bool {
+        self.rx.is_empty()
+    }
+    fn is_full(&self) -> bool {
+        self.rx.is_full()
+    }
+}
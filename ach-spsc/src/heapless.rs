@@ -1,6 +1,11 @@
+#[cfg(loom)]
+use core::array;
 use core::mem::MaybeUninit;
-use core::sync::atomic::{AtomicBool, AtomicUsize, Ordering::SeqCst};
-use core::{ptr, slice};
+use core::ptr;
+#[cfg(not(loom))]
+use core::slice;
+use util::sync::{AtomicBool, AtomicUsize, Ordering::SeqCst, UnsafeCell};
+use util::{CachePadded, Consumer, Counters, Error, Producer, Queue};
 
 pub struct Sender<'a, T, const N: usize> {
     spsc: &'a Spsc<T, N>,
@@ -37,13 +42,18 @@ impl<'a, T, const N: usize> Drop for Receiver<'a, T, N> {
 }
 
 pub struct Spsc<T, const N: usize> {
-    buf: MaybeUninit<[T; N]>,
+    buf: [UnsafeCell<MaybeUninit<T>>; N],
     /// always points to the first element
-    start: AtomicUsize,
-    end: AtomicUsize,
+    start: CachePadded<AtomicUsize>,
+    end: CachePadded<AtomicUsize>,
     has_sender: AtomicBool,
     has_receiver: AtomicBool,
+    stats: Counters,
 }
+// The slots are only accessed by the single sender or the single receiver.
+// Values are moved in and out but never shared, so `T: Send` is enough.
+unsafe impl<T: Send, const N: usize> Send for Spsc<T, N> {}
+unsafe impl<T: Send, const N: usize> Sync for Spsc<T, N> {}
 impl<T, const N: usize> Default for Spsc<T, N> {
     fn default() -> Self {
         Self::new()
@@ -51,13 +61,31 @@ impl<T, const N: usize> Default for Spsc<T, N> {
 }
 impl<T, const N: usize> Spsc<T, N> {
     const CAPACITY: usize = N;
+    /// With `N` of power of two, the indexes wrap at `usize::MAX` by themselves.
+    const WRAP_NATURAL: bool = N.is_power_of_two();
+    #[cfg(not(loom))]
+    #[allow(clippy::declare_interior_mutable_const)]
+    const INIT_BUF: UnsafeCell<MaybeUninit<T>> = UnsafeCell::new(MaybeUninit::uninit());
+    #[cfg(not(loom))]
     pub const fn new() -> Self {
         Spsc {
-            buf: MaybeUninit::uninit(),
-            end: AtomicUsize::new(0),
-            start: AtomicUsize::new(0),
+            buf: [Self::INIT_BUF; N],
+            end: CachePadded::new(AtomicUsize::new(0)),
+            start: CachePadded::new(AtomicUsize::new(0)),
             has_sender: AtomicBool::new(true),
             has_receiver: AtomicBool::new(true),
+            stats: Counters::new(),
+        }
+    }
+    #[cfg(loom)]
+    pub fn new() -> Self {
+        Spsc {
+            buf: array::from_fn(|_| UnsafeCell::new(MaybeUninit::uninit())),
+            end: CachePadded::new(AtomicUsize::new(0)),
+            start: CachePadded::new(AtomicUsize::new(0)),
+            has_sender: AtomicBool::new(true),
+            has_receiver: AtomicBool::new(true),
+            stats: Counters::new(),
         }
     }
     pub fn take_sender(&self) -> Option<Sender<'_, T, N>> {
@@ -84,6 +112,30 @@ impl<T, const N: usize> Spsc<T, N> {
     pub(crate) unsafe fn free_recver(&self) {
         self.has_receiver.store(true, SeqCst)
     }
+    /// Takes both the sender and the receiver, they are `'static` for a static Spsc.
+    ///
+    /// Returns None if either of them is taken.
+    pub fn split(&self) -> Option<(Sender<'_, T, N>, Receiver<'_, T, N>)> {
+        let sender = self.take_sender()?;
+        let recver = self.take_recver()?;
+        Some((sender, recver))
+    }
+    /// Same as `split`, but never fails, as no handle can be alive while `self` is borrowed mutably.
+    pub fn split_mut(&mut self) -> (Sender<'_, T, N>, Receiver<'_, T, N>) {
+        // Handles leaked by `mem::forget` may have left them taken.
+        self.has_sender.store(false, SeqCst);
+        self.has_receiver.store(false, SeqCst);
+        (Sender::new(self), Receiver::new(self))
+    }
+    /// Same as `new`, but the indexes start at `idx`, to test the wrapping of them.
+    #[doc(hidden)]
+    pub fn with_index(idx: usize) -> Self {
+        let spsc = Self::new();
+        spsc.start.store(idx, SeqCst);
+        spsc.end.store(idx, SeqCst);
+        spsc
+    }
+    #[cfg(not(loom))]
     fn ptr(&self) -> *mut T {
         self.buf.as_ptr() as *mut T
     }
@@ -94,7 +146,9 @@ impl<T, const N: usize> Spsc<T, N> {
         usize::MAX / Self::CAPACITY * Self::CAPACITY
     }
     fn wrap_len(&self, start: usize, end: usize) -> usize {
-        if end >= start {
+        if Self::WRAP_NATURAL {
+            end.wrapping_sub(start)
+        } else if end >= start {
             end - start
         } else {
             self.wrap_max() - start + end
@@ -111,26 +165,42 @@ impl<T, const N: usize> Spsc<T, N> {
     pub fn is_full(&self) -> bool {
         self.len() >= self.capacity()
     }
+    /// Snapshot of the counters of max len and failures.
+    #[cfg(feature = "stats")]
+    pub fn stats(&self) -> util::Stats {
+        self.stats.snapshot()
+    }
+    #[cfg(feature = "stats")]
+    pub fn reset_stats(&self) {
+        self.stats.reset()
+    }
     #[inline]
     unsafe fn buffer_read(&self, off: usize) -> T {
-        ptr::read(self.ptr().add(off))
+        self.buf[off].with(|p| ptr::read(p).assume_init())
     }
     #[inline]
     unsafe fn buffer_write(&self, off: usize, value: T) {
-        ptr::write(self.ptr().add(off), value);
+        self.buf[off].with_mut(|p| ptr::write(p, MaybeUninit::new(value)));
     }
     #[inline]
     fn index(&self, idx: usize) -> usize {
-        idx % Self::CAPACITY
+        if Self::WRAP_NATURAL {
+            idx & (Self::CAPACITY - 1)
+        } else {
+            idx % Self::CAPACITY
+        }
     }
     #[inline]
     fn next_idx(&self, old: usize) -> usize {
-        if old == self.wrap_max() - 1 {
+        if Self::WRAP_NATURAL {
+            old.wrapping_add(1)
+        } else if old == self.wrap_max() - 1 {
             0
         } else {
             old + 1
         }
     }
+    #[cfg(not(loom))]
     pub fn as_mut_slices(&mut self) -> (&mut [T], &mut [T]) {
         let ptr = self.ptr();
         let start = self.start.load(SeqCst);
@@ -152,18 +222,29 @@ impl<T, const N: usize> Spsc<T, N> {
             )
         }
     }
+    #[cfg(not(loom))]
     pub fn clear(&mut self) {
         let (a, b) = self.as_mut_slices();
+        let (a, b) = (a as *mut [T], b as *mut [T]);
+        // Empty before dropping, so the Spsc stays consistent if a `drop` panics.
+        self.end.store(0, SeqCst);
+        self.start.store(0, SeqCst);
+        let _b = util::defer(|| unsafe { ptr::drop_in_place(b) });
         unsafe { ptr::drop_in_place(a) };
-        unsafe { ptr::drop_in_place(b) };
+    }
+    /// The buffer of loom isn't contiguous, so pops every element.
+    #[cfg(loom)]
+    pub fn clear(&mut self) {
+        while self.pop().is_some() {}
         self.end.store(0, SeqCst);
         self.start.store(0, SeqCst);
     }
-    fn pop(&self) -> Option<T> {
+    pub(crate) fn pop(&self) -> Option<T> {
         let end = self.end.load(SeqCst);
         let start = self.start.load(SeqCst);
         let len = self.wrap_len(start, end);
         if len == 0 || len > self.capacity() {
+            self.stats.pop_empty();
             return None;
         }
 
@@ -174,11 +255,12 @@ impl<T, const N: usize> Spsc<T, N> {
             .unwrap();
         ret
     }
-    fn push(&self, value: T) -> Result<(), T> {
+    pub(crate) fn push(&self, value: T) -> Result<(), T> {
         let start = self.start.load(SeqCst);
         let end = self.end.load(SeqCst);
         let len = self.wrap_len(start, end);
         if len >= self.capacity() {
+            self.stats.push_failed();
             return Err(value);
         }
 
@@ -187,6 +269,7 @@ impl<T, const N: usize> Spsc<T, N> {
         self.end
             .compare_exchange(end, self.next_idx(end), SeqCst, SeqCst)
             .unwrap();
+        self.stats.observe_len(|| len + 1);
         Ok(())
     }
 }
@@ -195,3 +278,56 @@ impl<T, const N: usize> Drop for Spsc<T, N> {
         self.clear()
     }
 }
+
+impl<'a, T, const N: usize> Producer<T> for Sender<'a, T, N> {
+    fn try_push(&mut self, value: T) -> Result<(), Error<T>> {
+        self.try_send(value).map_err(Error::full)
+    }
+}
+impl<'a, T, const N: usize> Queue<T> for Sender<'a, T, N> {
+    fn capacity(&self) -> usize {
+        N
+    }
+    fn len(&self) -> usize {
+        self.spsc.len()
+    }
+    fn is_empty(&self) -> bool {
+        self.spsc.is_empty()
+    }
+    fn is_full(&self) -> bool {
+        self.spsc.is_full()
+    }
+}
+impl<'a, T, const N: usize> Consumer<T> for Receiver<'a, T, N> {
+    fn try_pop(&mut self) -> Result<T, Error<()>> {
+        self.try_recv().ok_or_else(Error::empty)
+    }
+}
+impl<'a, T, const N: usize> Queue<T> for Receiver<'a, T, N> {
+    fn capacity(&self) -> usize {
+        N
+    }
+    fn len(&self) -> usize {
+        self.spsc.len()
+    }
+    fn is_empty(&self) -> bool {
+        self.spsc.is_empty()
+    }
+    fn is_full(&self) -> bool {
+        self.spsc.is_full()
+    }
+}
+impl<T, const N: usize> Queue<T> for Spsc<T, N> {
+    fn capacity(&self) -> usize {
+        N
+    }
+    fn len(&self) -> usize {
+        Spsc::len(self)
+    }
+    fn is_empty(&self) -> bool {
+        Spsc::is_empty(self)
+    }
+    fn is_full(&self) -> bool {
+        Spsc::is_full(self)
+    }
+}
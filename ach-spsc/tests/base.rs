@@ -23,3 +23,21 @@ fn base() {
     assert!(recver.try_recv().is_none());
     assert!(sender.try_send(6).is_ok());
 }
+
+#[test]
+fn wrap_natural() {
+    // The indexes cross `usize::MAX` in the second round.
+    let spsc: Spsc<usize, 4> = Spsc::with_index(usize::MAX - 5);
+    let (mut sender, mut recver) = spsc.split().unwrap();
+    for round in 0..4 {
+        for i in 0..4 {
+            assert!(sender.try_send(round * 4 + i).is_ok());
+            assert_eq!(spsc.len(), i + 1);
+        }
+        assert_eq!(sender.try_send(0), Err(0));
+        for i in 0..4 {
+            assert_eq!(recver.try_recv(), Some(round * 4 + i));
+        }
+        assert_eq!(recver.try_recv(), None);
+    }
+}
@@ -0,0 +1,57 @@
+//! Model checked by `RUSTFLAGS="--cfg loom" cargo test --test loom --release`.
+#![cfg(loom)]
+use ach_spsc::heapless::Spsc;
+use loom::sync::Arc;
+use loom::thread;
+
+#[test]
+fn spsc() {
+    loom::model(|| {
+        let spsc = Arc::new(Spsc::<usize, 2>::new());
+        let producer = {
+            let spsc = spsc.clone();
+            thread::spawn(move || {
+                let mut sender = spsc.take_sender().unwrap();
+                for i in 0..3 {
+                    while sender.try_send(i).is_err() {
+                        thread::yield_now();
+                    }
+                }
+            })
+        };
+        let mut recver = spsc.take_recver().unwrap();
+        for i in 0..3 {
+            loop {
+                if let Some(v) = recver.try_recv() {
+                    assert_eq!(v, i);
+                    break;
+                }
+                thread::yield_now();
+            }
+        }
+        producer.join().unwrap();
+    });
+}
+
+#[test]
+fn drop_remaining() {
+    loom::model(|| {
+        let val = Arc::new(());
+        let spsc = Arc::new(Spsc::<Arc<()>, 2>::new());
+        let producer = {
+            let spsc = spsc.clone();
+            let val = val.clone();
+            thread::spawn(move || {
+                let mut sender = spsc.take_sender().unwrap();
+                let _ = sender.try_send(val.clone());
+                let _ = sender.try_send(val);
+            })
+        };
+        let mut recver = spsc.take_recver().unwrap();
+        drop(recver.try_recv());
+        drop(recver);
+        producer.join().unwrap();
+        drop(spsc);
+        assert_eq!(Arc::strong_count(&val), 1);
+    });
+}
@@ -0,0 +1,47 @@
+#![cfg(not(loom))]
+use ach_spsc::heapless::Spsc;
+use ach_test::PanicOnDrop;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static DROPS: AtomicUsize = AtomicUsize::new(0);
+
+#[test]
+fn clear() {
+    let mut spsc: Spsc<PanicOnDrop<'static>, 4> = Spsc::new();
+    {
+        let mut sender = spsc.take_sender().unwrap();
+        let mut recver = spsc.take_recver().unwrap();
+        // Wraps, so the values are in two slices.
+        for _ in 0..3 {
+            sender
+                .try_send(PanicOnDrop::counted(false, &DROPS))
+                .ok()
+                .unwrap();
+            drop(recver.try_recv().unwrap());
+        }
+        sender
+            .try_send(PanicOnDrop::counted(true, &DROPS))
+            .ok()
+            .unwrap();
+        for _ in 0..3 {
+            sender
+                .try_send(PanicOnDrop::counted(false, &DROPS))
+                .ok()
+                .unwrap();
+        }
+    }
+    DROPS.store(0, Ordering::SeqCst);
+    assert!(catch_unwind(AssertUnwindSafe(|| spsc.clear())).is_err());
+    // Every value is dropped once, and the Spsc is empty and usable.
+    assert_eq!(DROPS.load(Ordering::SeqCst), 4);
+    assert!(spsc.is_empty());
+    let mut sender = spsc.take_sender().unwrap();
+    let mut recver = spsc.take_recver().unwrap();
+    assert!(recver.try_recv().is_none());
+    sender
+        .try_send(PanicOnDrop::counted(false, &DROPS))
+        .ok()
+        .unwrap();
+    assert!(recver.try_recv().is_some());
+}
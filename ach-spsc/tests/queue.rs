@@ -0,0 +1,40 @@
+use ach_spsc::heapless::Spsc;
+use util::{Consumer, ErrorKind, Producer, Queue};
+
+fn fill<P: Producer<usize> + Queue<usize>>(p: &mut P) {
+    let mut i = 0;
+    while p.try_push(i).is_ok() {
+        i += 1;
+    }
+    assert_eq!(i, p.capacity());
+    assert!(p.is_full());
+}
+fn drain<C: Consumer<usize> + Queue<usize>>(c: &mut C) -> usize {
+    let mut n = 0;
+    while let Ok(x) = c.try_pop() {
+        assert_eq!(x, n);
+        n += 1;
+    }
+    assert_eq!(c.try_pop().unwrap_err().kind, ErrorKind::Empty);
+    assert!(c.is_empty());
+    n
+}
+
+#[test]
+fn heapless() {
+    static SPSC: Spsc<usize, 3> = Spsc::new();
+    let mut sender = SPSC.take_sender().unwrap();
+    let mut recver = SPSC.take_recver().unwrap();
+    fill(&mut sender);
+    assert_eq!(sender.try_push(3).unwrap_err().kind, ErrorKind::Full);
+    assert_eq!(Queue::len(&SPSC), 3);
+    assert_eq!(drain(&mut recver), 3);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn heap() {
+    let (mut tx, mut rx) = ach_spsc::heap::channel::<usize, 2>();
+    fill(&mut tx);
+    assert_eq!(drain(&mut rx), 2);
+}
@@ -0,0 +1,89 @@
+#![cfg(not(loom))]
+use ach_spsc::heapless::{Receiver, Sender, Spsc};
+use std::mem;
+use std::thread;
+
+#[test]
+fn split_static() {
+    static SPSC: Spsc<usize, 2> = Spsc::new();
+    let (mut sender, mut recver): (Sender<'static, _, 2>, Receiver<'static, _, 2>) =
+        SPSC.split().unwrap();
+    assert!(SPSC.split().is_none());
+    assert!(SPSC.take_sender().is_none());
+    let producer = thread::spawn(move || {
+        for i in 0..100 {
+            while sender.try_send(i).is_err() {
+                thread::yield_now();
+            }
+        }
+    });
+    for i in 0..100 {
+        loop {
+            if let Some(x) = recver.try_recv() {
+                assert_eq!(x, i);
+                break;
+            }
+            thread::yield_now();
+        }
+    }
+    producer.join().unwrap();
+
+    // Either end taken fails the split, and doesn't leak the other one.
+    assert!(SPSC.split().is_none());
+    drop(recver);
+    assert!(SPSC.split().is_some());
+    let sender = SPSC.take_sender().unwrap();
+    assert!(SPSC.split().is_none());
+    assert!(SPSC.take_recver().is_some());
+    drop(sender);
+    assert!(SPSC.split().is_some());
+}
+
+#[test]
+fn split_mut() {
+    let mut spsc: Spsc<usize, 2> = Spsc::new();
+    mem::forget(spsc.take_sender());
+    assert!(spsc.split().is_none());
+    let (mut sender, mut recver) = spsc.split_mut();
+    thread::scope(|s| {
+        s.spawn(|| {
+            for i in 0..100 {
+                while sender.try_send(i).is_err() {
+                    thread::yield_now();
+                }
+            }
+        });
+        for i in 0..100 {
+            loop {
+                if let Some(x) = recver.try_recv() {
+                    assert_eq!(x, i);
+                    break;
+                }
+                thread::yield_now();
+            }
+        }
+    });
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn channel() {
+    let (mut tx, mut rx) = ach_spsc::heap::channel::<usize, 2>();
+    let producer = thread::spawn(move || {
+        for i in 0..100 {
+            while tx.try_send(i).is_err() {
+                thread::yield_now();
+            }
+        }
+    });
+    for i in 0..100 {
+        loop {
+            if let Some(x) = rx.try_recv() {
+                assert_eq!(x, i);
+                break;
+            }
+            thread::yield_now();
+        }
+    }
+    producer.join().unwrap();
+}
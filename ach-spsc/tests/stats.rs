@@ -0,0 +1,26 @@
+#![cfg(feature = "stats")]
+use ach_spsc::heapless::Spsc;
+use util::Stats;
+
+#[test]
+fn base() {
+    let spsc: Spsc<usize, 3> = Spsc::new();
+    let mut sender = spsc.take_sender().unwrap();
+    let mut recver = spsc.take_recver().unwrap();
+    assert_eq!(spsc.stats(), Stats::default());
+    assert!(recver.try_recv().is_none());
+    assert!(sender.try_send(1).is_ok());
+    assert!(sender.try_send(2).is_ok());
+    assert_eq!(recver.try_recv(), Some(1));
+    assert!(sender.try_send(3).is_ok());
+    assert!(sender.try_send(4).is_ok());
+    assert!(sender.try_send(5).is_err());
+    let stats = spsc.stats();
+    assert_eq!(stats.max_len, 3);
+    assert_eq!(stats.push_failures, 1);
+    assert_eq!(stats.pop_empty, 1);
+    assert_eq!(stats.cas_retries, 0);
+
+    spsc.reset_stats();
+    assert_eq!(spsc.stats(), Stats::default());
+}
@@ -0,0 +1,80 @@
+//! Fixtures shared by the tests of the workspace.
+#![no_std]
+use core::cell::Cell;
+use core::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+/// Value which panics in `drop` if asked to, and counts its drops.
+pub struct PanicOnDrop<'a> {
+    panic: bool,
+    drops: Option<&'a AtomicUsize>,
+}
+impl PanicOnDrop<'static> {
+    pub const fn new(panic: bool) -> Self {
+        Self { panic, drops: None }
+    }
+}
+impl<'a> PanicOnDrop<'a> {
+    /// Same as `new`, and adds 1 to `drops` when dropped, before panicking.
+    pub const fn counted(panic: bool, drops: &'a AtomicUsize) -> Self {
+        Self {
+            panic,
+            drops: Some(drops),
+        }
+    }
+}
+impl<'a> Drop for PanicOnDrop<'a> {
+    fn drop(&mut self) {
+        if let Some(drops) = self.drops {
+            drops.fetch_add(1, Ordering::SeqCst);
+        }
+        if self.panic {
+            panic!("drop");
+        }
+    }
+}
+
+/// Value which panics in `clone` once `panic` is set, and clears it.
+#[derive(Debug)]
+pub struct PanicOnClone<'a> {
+    pub val: usize,
+    panic: &'a AtomicBool,
+}
+impl<'a> PanicOnClone<'a> {
+    pub const fn new(val: usize, panic: &'a AtomicBool) -> Self {
+        Self { val, panic }
+    }
+}
+impl<'a> Clone for PanicOnClone<'a> {
+    fn clone(&self) -> Self {
+        if self.panic.swap(false, Ordering::SeqCst) {
+            panic!("clone");
+        }
+        Self::new(self.val, self.panic)
+    }
+}
+impl<'a> PartialEq for PanicOnClone<'a> {
+    fn eq(&self, other: &Self) -> bool {
+        self.val == other.val
+    }
+}
+
+/// Clock which ticks once every time it is read.
+pub struct Ticks(pub Cell<u32>);
+impl Ticks {
+    pub const fn new() -> Self {
+        Self(Cell::new(0))
+    }
+}
+impl Default for Ticks {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl util::Clock for Ticks {
+    type Instant = u32;
+    fn now(&self) -> u32 {
+        let now = self.0.get();
+        self.0.set(now + 1);
+        now
+    }
+}
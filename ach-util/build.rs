@@ -0,0 +1,14 @@
+use std::env;
+
+fn main() {
+    // The cycle of `MemoryRing` is derived from the `usize` index, so 64-bit words need 64-bit pointers.
+    let has_atomic_64 = env::var("CARGO_CFG_TARGET_HAS_ATOMIC")
+        .map(|x| x.split(',').any(|x| x == "64"))
+        .unwrap_or(false);
+    if env::var_os("CARGO_FEATURE_WIDE_STATE").is_some()
+        && has_atomic_64
+        && env::var("CARGO_CFG_TARGET_POINTER_WIDTH").as_deref() == Ok("64")
+    {
+        println!("cargo:rustc-cfg=wide_state");
+    }
+}
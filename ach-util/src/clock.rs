@@ -0,0 +1,49 @@
+use spin_loop::{Backoff, Spin};
+
+/// Time source of the deadline-based operations, such as a cycle counter or `Instant`.
+pub trait Clock {
+    type Instant: PartialOrd;
+    fn now(&self) -> Self::Instant;
+}
+
+/// Gives up once the clock reaches the deadline.
+pub struct Deadline<'a, C: Clock, B = Spin> {
+    clock: &'a C,
+    deadline: C::Instant,
+    inner: B,
+}
+impl<'a, C: Clock> Deadline<'a, C> {
+    pub fn new(clock: &'a C, deadline: C::Instant) -> Self {
+        Self::with_backoff(clock, deadline, Spin)
+    }
+}
+impl<'a, C: Clock, B: Backoff> Deadline<'a, C, B> {
+    /// Waits by `inner` until the deadline.
+    pub fn with_backoff(clock: &'a C, deadline: C::Instant, inner: B) -> Self {
+        Self {
+            clock,
+            deadline,
+            inner,
+        }
+    }
+}
+impl<'a, C: Clock, B: Backoff> Backoff for Deadline<'a, C, B> {
+    fn snooze(&mut self) -> bool {
+        if self.clock.now() >= self.deadline {
+            return false;
+        }
+        self.inner.snooze()
+    }
+}
+impl<'a, C: Clock, B: Clone> Clone for Deadline<'a, C, B>
+where
+    C::Instant: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            clock: self.clock,
+            deadline: self.deadline.clone(),
+            inner: self.inner.clone(),
+        }
+    }
+}
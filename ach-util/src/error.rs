@@ -1,7 +1,61 @@
 use crate::MemoryState;
 use core::fmt;
+use spin_loop::{Backoff, Spin};
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// The container is full.
+    Full,
+    /// The container is empty, or the value is uninitialized.
+    Empty,
+    /// The value is in operation by others.
+    Busy,
+    /// The value is already initialized.
+    AlreadyInitialized,
+    /// The value is referenced.
+    Referenced,
+    /// The other side is dropped.
+    Disconnected,
+    /// Gave up retrying.
+    Timeout,
+    /// Any other failure, such as the ones of `Error::new`, or of the `MemoryState::Unknown` state.
+    Other,
+}
+impl From<MemoryState> for ErrorKind {
+    fn from(state: MemoryState) -> Self {
+        match state {
+            MemoryState::Uninitialized => Self::Empty,
+            MemoryState::Initialized => Self::AlreadyInitialized,
+            MemoryState::Initializing | MemoryState::Erasing | MemoryState::Regaining => Self::Busy,
+            MemoryState::Unknown => Self::Other,
+        }
+    }
+}
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let msg = match self {
+            Self::Full => "full",
+            Self::Empty => "empty",
+            Self::Busy => "busy",
+            Self::AlreadyInitialized => "already initialized",
+            Self::Referenced => "referenced",
+            Self::Disconnected => "disconnected",
+            Self::Timeout => "timeout",
+            Self::Other => "other error",
+        };
+        f.write_str(msg)
+    }
+}
+
+/// Error of an operation, which gives the input back.
+///
+/// Notice: `kind` was added as a public field, so an `Error` built by a struct expression
+/// has to set it too. This breaks such code of the earlier versions,
+/// `Error::new` and the constructors like `Error::full` are not affected.
 pub struct Error<T> {
+    /// What went wrong, derived from `state` unless the container knows better.
+    pub kind: ErrorKind,
     pub state: MemoryState,
     pub input: T,
     pub retry: bool,
@@ -9,45 +63,99 @@ pub struct Error<T> {
 impl<T> Error<T> {
     pub fn new(input: T) -> Self {
         Self {
+            kind: ErrorKind::Other,
             state: MemoryState::Unknown,
             input,
             retry: false,
         }
     }
+    pub fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+    /// Returns the input of the failed operation.
+    pub fn into_inner(self) -> T {
+        self.input
+    }
 }
 impl<T> fmt::Debug for Error<T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        fmt::Debug::fmt(&self.state, f)
+        f.debug_struct("Error")
+            .field("kind", &self.kind)
+            .field("state", &self.state)
+            .field("retry", &self.retry)
+            .finish()
+    }
+}
+impl<T> fmt::Display for Error<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.kind, f)
     }
 }
+#[cfg(feature = "std")]
+impl<T> std::error::Error for Error<T> {}
 
-pub fn retry<I, O, F>(mut f: F, mut input: I) -> Result<O, Error<I>>
+pub fn retry<I, O, F>(f: F, input: I) -> Result<O, Error<I>>
+where
+    F: FnMut(I) -> Result<O, Error<I>>,
+{
+    retry_with(Spin, f, input)
+}
+/// Retries `f` while the error is retryable, waiting by `backoff` between attempts.
+///
+/// Returns the last error with `ErrorKind::Timeout` if `backoff` gives up.
+pub fn retry_with<B, I, O, F>(mut backoff: B, mut f: F, mut input: I) -> Result<O, Error<I>>
 where
+    B: Backoff,
     F: FnMut(I) -> Result<O, Error<I>>,
 {
     loop {
         match f(input) {
             Ok(val) => return Ok(val),
             Err(err) if err.retry => {
+                if !backoff.snooze() {
+                    return Err(Error {
+                        kind: ErrorKind::Timeout,
+                        ..err
+                    });
+                }
                 input = err.input;
-                spin_loop::spin();
                 continue;
             }
             Err(err) => return Err(err),
         }
     }
 }
-pub fn unwrap<I, O, F>(mut f: F, mut input: I) -> O
+/// Retries `f` until it succeeds, even if the error isn't retryable.
+///
+/// Notice: `Spin`
+pub fn unwrap<I, O, F>(f: F, input: I) -> O
+where
+    F: FnMut(I) -> Result<O, Error<I>>,
+{
+    match unwrap_with(Spin, f, input) {
+        Ok(val) => val,
+        Err(_) => unreachable!(),
+    }
+}
+/// Retries `f` until it succeeds, even if the error isn't retryable, waiting by `backoff` between attempts.
+///
+/// Returns the last error with `ErrorKind::Timeout` if `backoff` gives up.
+pub fn unwrap_with<B, I, O, F>(mut backoff: B, mut f: F, mut input: I) -> Result<O, Error<I>>
 where
+    B: Backoff,
     F: FnMut(I) -> Result<O, Error<I>>,
 {
     loop {
         match f(input) {
-            Ok(val) => return val,
+            Ok(val) => return Ok(val),
             Err(err) => {
+                if !backoff.snooze() {
+                    return Err(Error {
+                        kind: ErrorKind::Timeout,
+                        ..err
+                    });
+                }
                 input = err.input;
-                spin_loop::spin();
-                continue;
             }
         }
     }
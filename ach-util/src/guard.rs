@@ -0,0 +1,17 @@
+/// Runs the closure when dropped, also when unwinding from a panic.
+///
+/// Used to leave a consistent state behind when user code, such as `T::drop`, panics in a transient state.
+pub struct Defer<F: FnOnce()> {
+    f: Option<F>,
+}
+impl<F: FnOnce()> Drop for Defer<F> {
+    fn drop(&mut self) {
+        if let Some(f) = self.f.take() {
+            f()
+        }
+    }
+}
+/// Runs `f` when the returned guard is dropped.
+pub fn defer<F: FnOnce()>(f: F) -> Defer<F> {
+    Defer { f: Some(f) }
+}
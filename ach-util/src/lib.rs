@@ -1,13 +1,31 @@
 #![no_std]
+#[cfg(feature = "std")]
+extern crate std;
 
+pub mod clock;
 pub mod error;
+pub mod guard;
 pub mod op;
+pub mod padded;
+pub mod queue;
 pub mod refer;
 pub mod ring;
+pub mod select;
 pub mod state;
+pub mod stats;
+pub mod sync;
+pub mod waker;
 
+pub use clock::*;
 pub use error::*;
+pub use guard::*;
 pub use op::*;
+pub use padded::*;
+pub use queue::*;
 pub use refer::*;
 pub use ring::*;
+pub use select::*;
+pub use spin_loop::{Backoff, Bounded, Exponential, Spin};
 pub use state::*;
+pub use stats::*;
+pub use waker::*;
@@ -1,3 +1,5 @@
+use crate::state::StateWord;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Op {
     None = 0,
@@ -25,45 +27,59 @@ impl From<Op> for u8 {
     }
 }
 
-pub type AtomicMemoryOp = atomic::Atomic<MemoryOp>;
+pub type AtomicMemoryOp = crate::sync::Atomic<MemoryOp>;
+
+/// Version of `MemoryOp`, 12 bits are used, or 28 bits with `StateWord` of `u64`.
+#[cfg(not(wide_state))]
+pub type Version = u16;
+/// Version of `MemoryOp`, 12 bits are used, or 28 bits with `StateWord` of `u64`.
+#[cfg(wide_state)]
+pub type Version = u32;
 
+/// Operation with current and next versions.
+///
+/// The operation is in the lowest byte, the current version is next to it, and the next version is in the highest bits.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
-pub struct MemoryOp(u32);
+pub struct MemoryOp(StateWord);
 impl Default for MemoryOp {
     fn default() -> Self {
         Self::new()
     }
 }
 impl MemoryOp {
+    const VERSION_BITS: u32 = (StateWord::BITS - 8) / 2;
+    const VERSION_MASK: StateWord = (1 << Self::VERSION_BITS) - 1;
+    const NEXT_SHIFT: u32 = 8 + Self::VERSION_BITS;
     pub const fn new() -> MemoryOp {
         Self(0)
     }
     pub fn op(&self) -> Op {
         Op::from((self.0 & 0xFF) as u8)
     }
-    pub fn cur_version(&self) -> u16 {
-        ((self.0 & 0x000FFF00) >> 8) as u16
+    pub fn cur_version(&self) -> Version {
+        ((self.0 >> 8) & Self::VERSION_MASK) as Version
     }
-    pub fn next_version(&mut self) -> u16 {
-        ((self.0 & 0xFFF00000) >> 20) as u16
+    pub fn next_version(&mut self) -> Version {
+        (self.0 >> Self::NEXT_SHIFT) as Version
     }
     pub fn finish(&mut self) {
-        self.0 = (self.0 & 0xFFF00000) | ((self.next_version() as u32) << 8)
+        self.0 = (self.0 & (Self::VERSION_MASK << Self::NEXT_SHIFT))
+            | ((self.next_version() as StateWord) << 8)
     }
     pub fn is_finished(&mut self) -> bool {
         self.cur_version() == self.next_version()
     }
-    pub fn set_op(&mut self, op: Op) -> u16 {
-        self.0 = (self.0.wrapping_add(0x00100000) & 0xFFFFFF00) | (op as u32);
+    pub fn set_op(&mut self, op: Op) -> Version {
+        self.0 = (self.0.wrapping_add(1 << Self::NEXT_SHIFT) & !0xFF) | (op as StateWord);
         self.next_version()
     }
 }
-impl From<u32> for MemoryOp {
-    fn from(s: u32) -> Self {
+impl From<StateWord> for MemoryOp {
+    fn from(s: StateWord) -> Self {
         Self(s)
     }
 }
-impl From<MemoryOp> for u32 {
+impl From<MemoryOp> for StateWord {
     fn from(s: MemoryOp) -> Self {
         s.0
     }
@@ -0,0 +1,47 @@
+use core::fmt;
+use core::ops::{Deref, DerefMut};
+
+/// Keeps the value on its own cache line with feature `cache-padded`, so cores writing
+/// the neighbours don't invalidate it. Otherwise it is a transparent wrapper.
+#[cfg_attr(
+    all(
+        feature = "cache-padded",
+        any(target_arch = "x86_64", target_arch = "aarch64")
+    ),
+    repr(align(128))
+)]
+#[cfg_attr(
+    all(
+        feature = "cache-padded",
+        not(any(target_arch = "x86_64", target_arch = "aarch64"))
+    ),
+    repr(align(64))
+)]
+#[derive(Default, Clone, Copy, PartialEq, Eq)]
+pub struct CachePadded<T> {
+    value: T,
+}
+impl<T> CachePadded<T> {
+    pub const fn new(value: T) -> Self {
+        Self { value }
+    }
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+impl<T> Deref for CachePadded<T> {
+    type Target = T;
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+impl<T> DerefMut for CachePadded<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.value
+    }
+}
+impl<T: fmt::Debug> fmt::Debug for CachePadded<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(&self.value, f)
+    }
+}
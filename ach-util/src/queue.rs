@@ -0,0 +1,81 @@
+use crate::{Error, ErrorKind, MemoryState};
+use core::task::Waker;
+
+/// Sending side of a container, such as a queue or the sender of a channel.
+///
+/// It takes `&mut self` for the single producer ones, the shared ones implement it for `&Container` too.
+pub trait Producer<T> {
+    /// Appends a value.
+    ///
+    /// Returns Err with the value if the container is full or in operation by others.
+    fn try_push(&mut self, value: T) -> Result<(), Error<T>>;
+}
+
+/// Receiving side of a container, such as a queue or the receiver of a channel.
+///
+/// It takes `&mut self` for the single consumer ones, the shared ones implement it for `&Container` too.
+pub trait Consumer<T> {
+    /// Removes a value.
+    ///
+    /// Returns Err if the container is empty or in operation by others.
+    fn try_pop(&mut self) -> Result<T, Error<()>>;
+    /// Registers `waker` to be woken when a value may be ready.
+    ///
+    /// Returns false if it isn't supported, which is the default,
+    /// so the waiting futures have to poll again by themselves.
+    fn register_waker(&mut self, waker: &Waker) -> bool {
+        let _ = waker;
+        false
+    }
+}
+
+/// Container of values `T` with a fixed capacity.
+pub trait Queue<T> {
+    fn capacity(&self) -> usize;
+    /// Number of values, it may be outdated as soon as returned.
+    fn len(&self) -> usize;
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    fn is_full(&self) -> bool {
+        self.len() >= self.capacity()
+    }
+}
+
+impl<T, Q: Queue<T> + ?Sized> Queue<T> for &Q {
+    fn capacity(&self) -> usize {
+        (**self).capacity()
+    }
+    fn len(&self) -> usize {
+        (**self).len()
+    }
+    fn is_empty(&self) -> bool {
+        (**self).is_empty()
+    }
+    fn is_full(&self) -> bool {
+        (**self).is_full()
+    }
+}
+
+impl<T> Error<T> {
+    /// Error of pushing to a full container.
+    pub fn full(input: T) -> Self {
+        Error {
+            kind: ErrorKind::Full,
+            state: MemoryState::Initialized,
+            input,
+            retry: false,
+        }
+    }
+}
+impl Error<()> {
+    /// Error of popping from an empty container.
+    pub fn empty() -> Self {
+        Error {
+            kind: ErrorKind::Empty,
+            state: MemoryState::Uninitialized,
+            input: (),
+            retry: false,
+        }
+    }
+}
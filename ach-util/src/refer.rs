@@ -1,32 +1,37 @@
-use super::state::MemoryState;
+use super::state::{MemoryState, StateWord};
 
-pub type AtomicMemoryRefer = atomic::Atomic<MemoryRefer>;
+pub type AtomicMemoryRefer = crate::sync::Atomic<MemoryRefer>;
 
+/// State and reference count of a value.
+///
+/// The state is in the highest byte, and the reference count is in the other bits of `StateWord`.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
-pub struct MemoryRefer(u32);
+pub struct MemoryRefer(StateWord);
 impl Default for MemoryRefer {
     fn default() -> Self {
         Self::new()
     }
 }
 impl MemoryRefer {
+    const STATE_SHIFT: u32 = StateWord::BITS - 8;
+    const REFER_MASK: StateWord = (1 << Self::STATE_SHIFT) - 1;
     /// Uninitialized
     pub const fn new() -> Self {
         Self(0)
     }
     pub fn state(&self) -> MemoryState {
-        ((self.0 >> 24) as u8).into()
+        ((self.0 >> Self::STATE_SHIFT) as u8).into()
     }
     pub fn set_state(&mut self, val: MemoryState) {
-        self.0 = (self.0 & 0x00FF_FFFF) | ((u8::from(val) as u32) << 24);
+        self.0 = (self.0 & Self::REFER_MASK) | ((u8::from(val) as StateWord) << Self::STATE_SHIFT);
     }
     pub const fn max_refer() -> usize {
-        0x00FF_FFFF
+        Self::REFER_MASK as usize
     }
     pub fn ref_num(&self) -> Result<usize, MemoryState> {
         let state = self.state();
         if state.is_initialized() || state.is_regaining() || state.is_erasing() {
-            Ok((self.0 as usize) & 0x00FF_FFFF)
+            Ok((self.0 & Self::REFER_MASK) as usize)
         } else {
             Err(state)
         }
@@ -65,12 +70,12 @@ impl From<MemoryState> for MemoryRefer {
         refer
     }
 }
-impl From<u32> for MemoryRefer {
-    fn from(s: u32) -> Self {
+impl From<StateWord> for MemoryRefer {
+    fn from(s: StateWord) -> Self {
         Self(s)
     }
 }
-impl From<MemoryRefer> for u32 {
+impl From<MemoryRefer> for StateWord {
     fn from(s: MemoryRefer) -> Self {
         s.0
     }
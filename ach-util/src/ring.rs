@@ -1,31 +1,40 @@
-use super::state::MemoryState;
+use super::state::{MemoryState, StateWord};
 use core::cmp::Ordering;
 
-pub type AtomicMemoryRing = atomic::Atomic<MemoryRing>;
+pub type AtomicMemoryRing = crate::sync::Atomic<MemoryRing>;
 
+/// State and cycle of a slot in ring.
+///
+/// The state is in the highest byte, and the cycle is in the lowest 24 bits, or 32 bits with `StateWord` of `u64`.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub struct MemoryRing(u32);
+pub struct MemoryRing(StateWord);
 impl MemoryRing {
     pub const INIT: Self = Self::new(0, MemoryState::Uninitialized);
+    const STATE_SHIFT: u32 = StateWord::BITS - 8;
+    #[cfg(not(wide_state))]
+    const CYCLE_MASK: StateWord = 0x00FF_FFFF;
+    #[cfg(wide_state)]
+    const CYCLE_MASK: StateWord = 0xFFFF_FFFF;
     pub const fn new(cycle: usize, state: MemoryState) -> Self {
-        let cycle = cycle & 0x00FF_FFFF;
-        Self((state as u32) << 24 | cycle as u32)
+        let cycle = cycle as StateWord & Self::CYCLE_MASK;
+        Self((state as StateWord) << Self::STATE_SHIFT | cycle)
     }
     pub const fn max_cycle() -> usize {
-        0x00FF_FFFF + 1
+        Self::CYCLE_MASK as usize + 1
     }
     pub fn cycle(&self) -> usize {
-        (self.0 as usize) & 0x00FF_FFFF
+        (self.0 & Self::CYCLE_MASK) as usize
     }
     pub fn set_cycle(&mut self, val: usize) {
-        let val = val & 0x00FF_FFFF;
-        self.0 = (self.0 & 0xFF00_0000) | val as u32;
+        let val = val as StateWord & Self::CYCLE_MASK;
+        self.0 = (self.0 & !Self::CYCLE_MASK) | val;
     }
     pub fn state(&self) -> MemoryState {
-        ((self.0 >> 24) as u8).into()
+        ((self.0 >> Self::STATE_SHIFT) as u8).into()
     }
     pub fn set_state(&mut self, val: MemoryState) {
-        self.0 = (self.0 & 0x00FF_FFFF) | ((u8::from(val) as u32) << 24);
+        let mask = (0xFF as StateWord) << Self::STATE_SHIFT;
+        self.0 = (self.0 & !mask) | ((u8::from(val) as StateWord) << Self::STATE_SHIFT);
     }
     pub fn next(&self) -> Self {
         let mut ret = *self;
@@ -51,12 +60,12 @@ impl MemoryRing {
         idx / size
     }
 }
-impl From<u32> for MemoryRing {
-    fn from(s: u32) -> Self {
+impl From<StateWord> for MemoryRing {
+    fn from(s: StateWord) -> Self {
         Self(s)
     }
 }
-impl From<MemoryRing> for u32 {
+impl From<MemoryRing> for StateWord {
     fn from(s: MemoryRing) -> Self {
         s.0
     }
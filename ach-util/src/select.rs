@@ -0,0 +1,247 @@
+use crate::{Clock, Consumer, Deadline, Error, ErrorKind};
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll, Waker};
+use spin_loop::{Backoff, Bounded, Spin};
+#[cfg(feature = "std")]
+use std::{sync::Arc, task::Wake, thread, time::Instant};
+
+/// Receives from whichever of several consumers is ready, the consumers may be of different types.
+///
+/// The consumers are tried in rotation, starting after the one which received last time,
+/// so a busy one can't starve the others.
+///
+/// `select_async`, and `select` with feature `std`, sleep until a push wakes them,
+/// if every consumer supports `Consumer::register_waker`, such as the ones built on `Ring`.
+/// Otherwise they poll again at once, and keep a core busy while waiting.
+pub struct Selector<'a, T, const N: usize> {
+    arms: [Option<&'a mut (dyn Consumer<T> + 'a)>; N],
+    len: usize,
+    next: usize,
+}
+impl<'a, T, const N: usize> Default for Selector<'a, T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl<'a, T, const N: usize> Selector<'a, T, N> {
+    const NONE: Option<&'a mut (dyn Consumer<T> + 'a)> = None;
+    pub const fn new() -> Self {
+        Self {
+            arms: [Self::NONE; N],
+            len: 0,
+            next: 0,
+        }
+    }
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+    /// Adds a consumer, and returns its index in the results.
+    ///
+    /// Returns Err with the consumer if the selector is full.
+    pub fn add(
+        &mut self,
+        consumer: &'a mut (dyn Consumer<T> + 'a),
+    ) -> Result<usize, &'a mut (dyn Consumer<T> + 'a)> {
+        if self.len >= N {
+            return Err(consumer);
+        }
+        self.arms[self.len] = Some(consumer);
+        self.len += 1;
+        Ok(self.len - 1)
+    }
+    /// Same as `add`, but for building in a chain.
+    ///
+    /// Panics if the selector is full.
+    pub fn with(mut self, consumer: &'a mut (dyn Consumer<T> + 'a)) -> Self {
+        if self.add(consumer).is_err() {
+            panic!("Selector is full");
+        }
+        self
+    }
+
+    /// Receives a value from the first ready consumer, and returns it with the index of the consumer.
+    ///
+    /// Returns Err if all of them are empty or in operation by others.
+    pub fn try_select(&mut self) -> Result<(usize, T), Error<()>> {
+        let mut err = Error::empty();
+        for i in 0..self.len {
+            let index = (self.next + i) % self.len;
+            let arm = self.arms[index].as_mut().unwrap();
+            match arm.try_pop() {
+                Ok(val) => {
+                    self.next = index + 1;
+                    return Ok((index, val));
+                }
+                Err(e) if e.retry => err = e,
+                Err(_) => {}
+            }
+        }
+        Err(err)
+    }
+    /// Registers `waker` on every consumer, returns false if any of them doesn't support it.
+    fn register_waker(&mut self, waker: &Waker) -> bool {
+        let mut all = true;
+        for arm in self.arms[..self.len].iter_mut() {
+            all &= arm.as_mut().unwrap().register_waker(waker);
+        }
+        all
+    }
+    /// Receives a value from the first ready consumer, and returns it with the index of the consumer.
+    ///
+    /// Notice: `Spin`
+    #[cfg(not(feature = "std"))]
+    pub fn select(&mut self) -> (usize, T) {
+        loop {
+            if let Ok(ret) = self.try_select() {
+                return ret;
+            }
+            spin_loop::spin();
+        }
+    }
+    /// Receives a value from the first ready consumer, and returns it with the index of the consumer.
+    ///
+    /// The thread is parked until a push wakes it, if every consumer supports `Consumer::register_waker`.
+    #[cfg(feature = "std")]
+    pub fn select(&mut self) -> (usize, T) {
+        let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+        loop {
+            if let Ok(ret) = self.park(&waker, None) {
+                return ret;
+            }
+        }
+    }
+    /// Receives a value, or parks the thread until a push wakes it or `deadline` is reached.
+    ///
+    /// Spins once instead of parking, if any consumer doesn't support `Consumer::register_waker`,
+    /// or is in operation by others.
+    #[cfg(feature = "std")]
+    fn park(&mut self, waker: &Waker, deadline: Option<Instant>) -> Result<(usize, T), Error<()>> {
+        match self.try_select() {
+            Err(err) if !err.retry && self.register_waker(waker) => {}
+            ret => {
+                spin_loop::spin();
+                return ret;
+            }
+        }
+        // A value may have arrived before the waker was registered.
+        let err = match self.try_select() {
+            Ok(ret) => return Ok(ret),
+            Err(err) => err,
+        };
+        match deadline {
+            Some(deadline) => {
+                thread::park_timeout(deadline.saturating_duration_since(Instant::now()))
+            }
+            None => thread::park(),
+        }
+        Err(err)
+    }
+    /// Same as `select`, but waits by `backoff` between attempts.
+    ///
+    /// Returns Err with `ErrorKind::Timeout` if `backoff` gives up.
+    pub fn select_with<B: Backoff>(&mut self, mut backoff: B) -> Result<(usize, T), Error<()>> {
+        loop {
+            match self.try_select() {
+                Ok(ret) => return Ok(ret),
+                Err(err) => {
+                    if !backoff.snooze() {
+                        return Err(Error {
+                            kind: ErrorKind::Timeout,
+                            ..err
+                        });
+                    }
+                }
+            }
+        }
+    }
+    /// Same as `select`, but gives up after `attempts` retries.
+    pub fn select_for(&mut self, attempts: usize) -> Result<(usize, T), Error<()>> {
+        self.select_with(Bounded::new(Spin, attempts))
+    }
+    /// Same as `select`, but gives up once `clock` reaches `deadline`.
+    pub fn select_until<C: Clock>(
+        &mut self,
+        clock: &C,
+        deadline: C::Instant,
+    ) -> Result<(usize, T), Error<()>> {
+        self.select_with(Deadline::new(clock, deadline))
+    }
+    /// Same as `select`, but gives up after `timeout`.
+    #[cfg(feature = "std")]
+    pub fn select_timeout(
+        &mut self,
+        timeout: std::time::Duration,
+    ) -> Result<(usize, T), Error<()>> {
+        let deadline = Instant::now() + timeout;
+        let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+        loop {
+            match self.park(&waker, Some(deadline)) {
+                Ok(ret) => return Ok(ret),
+                Err(err) if Instant::now() >= deadline => {
+                    return Err(Error {
+                        kind: ErrorKind::Timeout,
+                        ..err
+                    })
+                }
+                Err(_) => {}
+            }
+        }
+    }
+    /// Receives a value asynchronously, from the first ready consumer.
+    ///
+    /// When pending, it registers the waker on every consumer by `Consumer::register_waker`,
+    /// so it is polled again after a push.
+    /// If any of them doesn't support it, it wakes itself at once instead,
+    /// and the executor polls it again after the other ready tasks, so it busy-polls.
+    pub fn select_async(&mut self) -> Select<'_, 'a, T, N> {
+        Select { selector: self }
+    }
+}
+
+/// Future of `Selector::select_async`.
+pub struct Select<'s, 'a, T, const N: usize> {
+    selector: &'s mut Selector<'a, T, N>,
+}
+impl<'s, 'a, T, const N: usize> Future for Select<'s, 'a, T, N> {
+    type Output = (usize, T);
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match self.selector.try_select() {
+            Ok(ret) => return Poll::Ready(ret),
+            // No push may come to wake it.
+            Err(err) if err.retry => {
+                cx.waker().wake_by_ref();
+                return Poll::Pending;
+            }
+            Err(_) => {}
+        }
+        if !self.selector.register_waker(cx.waker()) {
+            cx.waker().wake_by_ref();
+            return Poll::Pending;
+        }
+        // A value may have arrived before the waker was registered.
+        match self.selector.try_select() {
+            Ok(ret) => Poll::Ready(ret),
+            Err(_) => Poll::Pending,
+        }
+    }
+}
+
+/// Waker which unparks a thread blocked in `Selector::select`.
+#[cfg(feature = "std")]
+struct ThreadWaker(thread::Thread);
+#[cfg(feature = "std")]
+impl Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark()
+    }
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.0.unpark()
+    }
+}
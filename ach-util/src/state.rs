@@ -1,4 +1,15 @@
-pub type AtomicMemoryState = atomic::Atomic<MemoryState>;
+pub type AtomicMemoryState = crate::sync::Atomic<MemoryState>;
+
+/// Word of the packed states `MemoryRing`, `MemoryRefer` and `MemoryOp`.
+///
+/// It is `u64` with feature `wide-state` on 64-bit targets which have 64-bit atomics, otherwise `u32`.
+#[cfg(wide_state)]
+pub type StateWord = u64;
+/// Word of the packed states `MemoryRing`, `MemoryRefer` and `MemoryOp`.
+///
+/// It is `u64` with feature `wide-state` on 64-bit targets which have 64-bit atomics, otherwise `u32`.
+#[cfg(not(wide_state))]
+pub type StateWord = u32;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub enum MemoryState {
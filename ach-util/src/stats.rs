@@ -0,0 +1,95 @@
+#[cfg(feature = "stats")]
+use crate::sync::{AtomicUsize, Ordering::Relaxed};
+
+/// Snapshot of the counters of a container.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    /// Max len observed after a push.
+    pub max_len: usize,
+    /// Pushes which failed as the container was full.
+    pub push_failures: usize,
+    /// Pops which failed as the container was empty.
+    pub pop_empty: usize,
+    /// Retries of compare-and-swap on the indexes, as other threads won the race.
+    pub cas_retries: usize,
+}
+
+/// Counters of a container, which are updated with feature `stats`.
+///
+/// Without the feature it is empty, and the updates compile to nothing.
+pub struct Counters {
+    #[cfg(feature = "stats")]
+    max_len: AtomicUsize,
+    #[cfg(feature = "stats")]
+    push_failures: AtomicUsize,
+    #[cfg(feature = "stats")]
+    pop_empty: AtomicUsize,
+    #[cfg(feature = "stats")]
+    cas_retries: AtomicUsize,
+}
+impl Default for Counters {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl Counters {
+    crate::const_fn! {
+        pub fn new() -> Self {
+            Self {
+                #[cfg(feature = "stats")]
+                max_len: AtomicUsize::new(0),
+                #[cfg(feature = "stats")]
+                push_failures: AtomicUsize::new(0),
+                #[cfg(feature = "stats")]
+                pop_empty: AtomicUsize::new(0),
+                #[cfg(feature = "stats")]
+                cas_retries: AtomicUsize::new(0),
+            }
+        }
+    }
+    /// Records the len after a push, `len` is only called with feature `stats`.
+    #[inline]
+    pub fn observe_len<F: FnOnce() -> usize>(&self, len: F) {
+        #[cfg(feature = "stats")]
+        self.max_len.fetch_max(len(), Relaxed);
+        #[cfg(not(feature = "stats"))]
+        let _ = len;
+    }
+    #[inline]
+    pub fn push_failed(&self) {
+        #[cfg(feature = "stats")]
+        self.push_failures.fetch_add(1, Relaxed);
+    }
+    #[inline]
+    pub fn pop_empty(&self) {
+        #[cfg(feature = "stats")]
+        self.pop_empty.fetch_add(1, Relaxed);
+    }
+    #[inline]
+    pub fn cas_retry(&self) {
+        #[cfg(feature = "stats")]
+        self.cas_retries.fetch_add(1, Relaxed);
+    }
+    /// Snapshot of the counters, they are all 0 without feature `stats`.
+    pub fn snapshot(&self) -> Stats {
+        #[cfg(feature = "stats")]
+        return Stats {
+            max_len: self.max_len.load(Relaxed),
+            push_failures: self.push_failures.load(Relaxed),
+            pop_empty: self.pop_empty.load(Relaxed),
+            cas_retries: self.cas_retries.load(Relaxed),
+        };
+        #[cfg(not(feature = "stats"))]
+        Stats::default()
+    }
+    /// Resets the counters to 0.
+    pub fn reset(&self) {
+        #[cfg(feature = "stats")]
+        {
+            self.max_len.store(0, Relaxed);
+            self.push_failures.store(0, Relaxed);
+            self.pop_empty.store(0, Relaxed);
+            self.cas_retries.store(0, Relaxed);
+        }
+    }
+}
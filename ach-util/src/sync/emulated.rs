@@ -0,0 +1,222 @@
+//! Atomics for single-core targets without compare-and-swap, such as Cortex-M0 and RV32IMC.
+//!
+//! Loads and stores are native, read-modify-write operations run in critical section.
+//! All of them are `SeqCst`, whatever the ordering argument is.
+
+use core::cell::UnsafeCell;
+use core::fmt;
+use core::sync::atomic::{self, Ordering, Ordering::SeqCst};
+use core::{mem, ptr, slice};
+use interrupt::CriticalSection;
+
+macro_rules! emulate {
+    ($(#[$attr:meta])* $name:ident<$($t:ident)?>($val:ty)) => {
+        $(#[$attr])*
+        #[repr(transparent)]
+        pub struct $name<$($t)?>(atomic::$name<$($t)?>);
+        impl<$($t)?> $name<$($t)?> {
+            pub const fn new(v: $val) -> Self {
+                Self(atomic::$name::new(v))
+            }
+            pub fn get_mut(&mut self) -> &mut $val {
+                self.0.get_mut()
+            }
+            pub fn into_inner(self) -> $val {
+                self.0.into_inner()
+            }
+            pub fn load(&self, _order: Ordering) -> $val {
+                self.0.load(SeqCst)
+            }
+            pub fn store(&self, val: $val, _order: Ordering) {
+                self.0.store(val, SeqCst)
+            }
+            pub fn swap(&self, val: $val, _order: Ordering) -> $val {
+                let _cs = CriticalSection::new();
+                let old = self.0.load(SeqCst);
+                self.0.store(val, SeqCst);
+                old
+            }
+            pub fn compare_exchange(
+                &self,
+                current: $val,
+                new: $val,
+                _success: Ordering,
+                _failure: Ordering,
+            ) -> Result<$val, $val> {
+                let _cs = CriticalSection::new();
+                let old = self.0.load(SeqCst);
+                if old == current {
+                    self.0.store(new, SeqCst);
+                    Ok(old)
+                } else {
+                    Err(old)
+                }
+            }
+            pub fn compare_exchange_weak(
+                &self,
+                current: $val,
+                new: $val,
+                success: Ordering,
+                failure: Ordering,
+            ) -> Result<$val, $val> {
+                self.compare_exchange(current, new, success, failure)
+            }
+            pub fn fetch_update<F>(
+                &self,
+                _set_order: Ordering,
+                _fetch_order: Ordering,
+                mut f: F,
+            ) -> Result<$val, $val>
+            where
+                F: FnMut($val) -> Option<$val>,
+            {
+                let _cs = CriticalSection::new();
+                let old = self.0.load(SeqCst);
+                match f(old) {
+                    Some(new) => {
+                        self.0.store(new, SeqCst);
+                        Ok(old)
+                    }
+                    None => Err(old),
+                }
+            }
+        }
+        impl<$($t)?> fmt::Debug for $name<$($t)?> {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                fmt::Debug::fmt(&self.0, f)
+            }
+        }
+    };
+}
+
+macro_rules! emulate_int {
+    ($($name:ident($val:ty)),*) => {$(
+        emulate!($name<>($val));
+        impl $name {
+            pub fn fetch_add(&self, val: $val, order: Ordering) -> $val {
+                self.fetch_update(order, order, |x| Some(x.wrapping_add(val))).unwrap()
+            }
+            pub fn fetch_sub(&self, val: $val, order: Ordering) -> $val {
+                self.fetch_update(order, order, |x| Some(x.wrapping_sub(val))).unwrap()
+            }
+            pub fn fetch_max(&self, val: $val, order: Ordering) -> $val {
+                self.fetch_update(order, order, |x| Some(x.max(val))).unwrap()
+            }
+            pub fn fetch_or(&self, val: $val, order: Ordering) -> $val {
+                self.fetch_update(order, order, |x| Some(x | val)).unwrap()
+            }
+            pub fn fetch_and(&self, val: $val, order: Ordering) -> $val {
+                self.fetch_update(order, order, |x| Some(x & val)).unwrap()
+            }
+        }
+        impl Default for $name {
+            fn default() -> Self {
+                Self::new(0)
+            }
+        }
+    )*};
+}
+
+emulate!(AtomicBool<>(bool));
+emulate!(AtomicPtr<T>(*mut T));
+emulate_int!(AtomicU8(u8), AtomicU32(u32), AtomicUsize(usize));
+
+impl Default for AtomicBool {
+    fn default() -> Self {
+        Self::new(false)
+    }
+}
+
+/// `atomic::Atomic` of which every operation runs in critical section.
+#[repr(transparent)]
+pub struct Atomic<T> {
+    v: UnsafeCell<T>,
+}
+unsafe impl<T: Copy + Send> Sync for Atomic<T> {}
+impl<T: Copy> Atomic<T> {
+    pub const fn new(v: T) -> Self {
+        Self {
+            v: UnsafeCell::new(v),
+        }
+    }
+    pub fn get_mut(&mut self) -> &mut T {
+        self.v.get_mut()
+    }
+    pub fn into_inner(self) -> T {
+        self.v.into_inner()
+    }
+    /// Compares by bytes as `atomic::Atomic` does, `T` needn't be `PartialEq`.
+    fn bytes_eq(a: &T, b: &T) -> bool {
+        let size = mem::size_of::<T>();
+        unsafe {
+            slice::from_raw_parts(a as *const T as *const u8, size)
+                == slice::from_raw_parts(b as *const T as *const u8, size)
+        }
+    }
+    pub fn load(&self, _order: Ordering) -> T {
+        let _cs = CriticalSection::new();
+        unsafe { ptr::read(self.v.get()) }
+    }
+    pub fn store(&self, val: T, _order: Ordering) {
+        let _cs = CriticalSection::new();
+        unsafe { ptr::write(self.v.get(), val) }
+    }
+    pub fn swap(&self, val: T, _order: Ordering) -> T {
+        let _cs = CriticalSection::new();
+        unsafe { ptr::replace(self.v.get(), val) }
+    }
+    pub fn compare_exchange(
+        &self,
+        current: T,
+        new: T,
+        _success: Ordering,
+        _failure: Ordering,
+    ) -> Result<T, T> {
+        let _cs = CriticalSection::new();
+        let old = unsafe { ptr::read(self.v.get()) };
+        if Self::bytes_eq(&old, &current) {
+            unsafe { ptr::write(self.v.get(), new) };
+            Ok(old)
+        } else {
+            Err(old)
+        }
+    }
+    pub fn compare_exchange_weak(
+        &self,
+        current: T,
+        new: T,
+        success: Ordering,
+        failure: Ordering,
+    ) -> Result<T, T> {
+        self.compare_exchange(current, new, success, failure)
+    }
+    pub fn fetch_update<F>(
+        &self,
+        _set_order: Ordering,
+        _fetch_order: Ordering,
+        mut f: F,
+    ) -> Result<T, T>
+    where
+        F: FnMut(T) -> Option<T>,
+    {
+        let _cs = CriticalSection::new();
+        let old = unsafe { ptr::read(self.v.get()) };
+        match f(old) {
+            Some(new) => {
+                unsafe { ptr::write(self.v.get(), new) };
+                Ok(old)
+            }
+            None => Err(old),
+        }
+    }
+}
+impl<T: Copy + Default> Default for Atomic<T> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+impl<T: Copy + fmt::Debug> fmt::Debug for Atomic<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Atomic").field(&self.load(SeqCst)).finish()
+    }
+}
@@ -0,0 +1,93 @@
+use crate::{MemoryOp, MemoryRefer, MemoryRing, MemoryState, StateWord};
+use loom::sync::atomic::{AtomicU64, Ordering};
+use core::marker::PhantomData;
+
+/// Types which can be stored in `Atomic` under loom.
+pub trait AtomicRepr: Copy {
+    fn into_bits(self) -> u64;
+    fn from_bits(bits: u64) -> Self;
+}
+impl AtomicRepr for MemoryState {
+    fn into_bits(self) -> u64 {
+        u8::from(self) as u64
+    }
+    fn from_bits(bits: u64) -> Self {
+        (bits as u8).into()
+    }
+}
+macro_rules! impl_repr {
+    ($($t:ty),*) => {$(
+        impl AtomicRepr for $t {
+            fn into_bits(self) -> u64 {
+                StateWord::from(self) as u64
+            }
+            fn from_bits(bits: u64) -> Self {
+                (bits as StateWord).into()
+            }
+        }
+    )*};
+}
+impl_repr!(MemoryOp, MemoryRefer, MemoryRing);
+
+/// `atomic::Atomic` backed by `loom::sync::atomic::AtomicU64`.
+pub struct Atomic<T> {
+    v: AtomicU64,
+    _t: PhantomData<T>,
+}
+impl<T: AtomicRepr> Atomic<T> {
+    pub fn new(v: T) -> Self {
+        Self {
+            v: AtomicU64::new(v.into_bits()),
+            _t: PhantomData,
+        }
+    }
+    pub fn load(&self, order: Ordering) -> T {
+        T::from_bits(self.v.load(order))
+    }
+    pub fn store(&self, val: T, order: Ordering) {
+        self.v.store(val.into_bits(), order)
+    }
+    pub fn swap(&self, val: T, order: Ordering) -> T {
+        T::from_bits(self.v.swap(val.into_bits(), order))
+    }
+    pub fn compare_exchange(
+        &self,
+        current: T,
+        new: T,
+        success: Ordering,
+        failure: Ordering,
+    ) -> Result<T, T> {
+        self.v
+            .compare_exchange(current.into_bits(), new.into_bits(), success, failure)
+            .map(T::from_bits)
+            .map_err(T::from_bits)
+    }
+    pub fn compare_exchange_weak(
+        &self,
+        current: T,
+        new: T,
+        success: Ordering,
+        failure: Ordering,
+    ) -> Result<T, T> {
+        self.v
+            .compare_exchange_weak(current.into_bits(), new.into_bits(), success, failure)
+            .map(T::from_bits)
+            .map_err(T::from_bits)
+    }
+    pub fn fetch_update<F>(
+        &self,
+        set_order: Ordering,
+        fetch_order: Ordering,
+        mut f: F,
+    ) -> Result<T, T>
+    where
+        F: FnMut(T) -> Option<T>,
+    {
+        self.v
+            .fetch_update(set_order, fetch_order, |x| {
+                f(T::from_bits(x)).map(T::into_bits)
+            })
+            .map(T::from_bits)
+            .map_err(T::from_bits)
+    }
+}
@@ -0,0 +1,74 @@
+//! Synchronization primitives used by the containers.
+//!
+//! They are the ones of `core` normally, the ones of [loom](https://docs.rs/loom) when built with `--cfg loom`,
+//! so the containers can be model checked by loom, and emulated ones on targets without compare-and-swap.
+
+#[cfg(all(not(loom), target_has_atomic = "ptr"))]
+pub use atomic::Atomic;
+#[cfg(all(not(loom), target_has_atomic = "ptr"))]
+pub use core::sync::atomic::{AtomicBool, AtomicPtr, AtomicU32, AtomicU8, AtomicUsize};
+#[cfg(not(loom))]
+pub use core::sync::atomic::Ordering;
+
+#[cfg(all(not(loom), not(target_has_atomic = "ptr")))]
+mod emulated;
+#[cfg(all(not(loom), not(target_has_atomic = "ptr")))]
+pub use self::emulated::{Atomic, AtomicBool, AtomicPtr, AtomicU32, AtomicU8, AtomicUsize};
+
+#[cfg(loom)]
+mod loom_atomic;
+#[cfg(loom)]
+pub use self::loom_atomic::{Atomic, AtomicRepr};
+#[cfg(loom)]
+pub use loom::cell::UnsafeCell;
+#[cfg(loom)]
+pub use loom::sync::atomic::{AtomicBool, AtomicPtr, AtomicU32, AtomicU8, AtomicUsize, Ordering};
+
+/// Declares a function which is `const fn` normally, and plain `fn` under loom.
+///
+/// The primitives of loom can't be created in const context.
+#[cfg(not(loom))]
+#[macro_export]
+macro_rules! const_fn {
+    ($(#[$attr:meta])* $vis:vis fn $($rest:tt)*) => {
+        $(#[$attr])* $vis const fn $($rest)*
+    };
+}
+/// Declares a function which is `const fn` normally, and plain `fn` under loom.
+///
+/// The primitives of loom can't be created in const context.
+#[cfg(loom)]
+#[macro_export]
+macro_rules! const_fn {
+    ($(#[$attr:meta])* $vis:vis fn $($rest:tt)*) => {
+        $(#[$attr])* $vis fn $($rest)*
+    };
+}
+
+/// `core::cell::UnsafeCell` with the API of `loom::cell::UnsafeCell`.
+#[cfg(not(loom))]
+#[derive(Debug)]
+#[repr(transparent)]
+pub struct UnsafeCell<T: ?Sized>(core::cell::UnsafeCell<T>);
+#[cfg(not(loom))]
+impl<T> UnsafeCell<T> {
+    pub const fn new(data: T) -> Self {
+        Self(core::cell::UnsafeCell::new(data))
+    }
+    pub fn into_inner(self) -> T {
+        self.0.into_inner()
+    }
+}
+#[cfg(not(loom))]
+impl<T: ?Sized> UnsafeCell<T> {
+    /// Reads the value by an immutable pointer.
+    #[inline]
+    pub fn with<R>(&self, f: impl FnOnce(*const T) -> R) -> R {
+        f(self.0.get())
+    }
+    /// Writes the value by a mutable pointer.
+    #[inline]
+    pub fn with_mut<R>(&self, f: impl FnOnce(*mut T) -> R) -> R {
+        f(self.0.get())
+    }
+}
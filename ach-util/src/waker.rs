@@ -0,0 +1,97 @@
+use crate::sync::{
+    AtomicU8,
+    Ordering::{AcqRel, Acquire, Release},
+    UnsafeCell,
+};
+use core::fmt;
+use core::task::Waker;
+
+/// Slot of one `Waker`, registered by a waiting consumer and woken by a producer.
+///
+/// It never spins, so it can be woken from an interrupt preempting a registration.
+/// A new registration replaces the waker of another task, which is woken to register again.
+pub struct AtomicWaker {
+    state: AtomicU8,
+    waker: UnsafeCell<Option<Waker>>,
+}
+// The waker is only accessed by the owner of `REGISTERING` or `WAKING`.
+unsafe impl Send for AtomicWaker {}
+unsafe impl Sync for AtomicWaker {}
+impl Default for AtomicWaker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl AtomicWaker {
+    const WAITING: u8 = 0;
+    const REGISTERING: u8 = 1;
+    const WAKING: u8 = 2;
+
+    crate::const_fn! {
+        pub fn new() -> Self {
+            Self {
+                state: AtomicU8::new(Self::WAITING),
+                waker: UnsafeCell::new(None),
+            }
+        }
+    }
+
+    /// Registers `waker` to be woken by the next `wake`.
+    pub fn register(&self, waker: &Waker) {
+        match self
+            .state
+            .compare_exchange(Self::WAITING, Self::REGISTERING, Acquire, Acquire)
+        {
+            Ok(_) => {
+                let prev = self.waker.with_mut(|p| match unsafe { &mut *p } {
+                    Some(old) if old.will_wake(waker) => None,
+                    slot => slot.replace(waker.clone()),
+                });
+                if let Err(state) =
+                    self.state
+                        .compare_exchange(Self::REGISTERING, Self::WAITING, AcqRel, Acquire)
+                {
+                    // Woken while registering, so wakes it at once.
+                    debug_assert_eq!(state, Self::REGISTERING | Self::WAKING);
+                    let waker = self.waker.with_mut(|p| unsafe { (*p).take() });
+                    self.state.swap(Self::WAITING, AcqRel);
+                    if let Some(waker) = waker {
+                        waker.wake();
+                    }
+                }
+                if let Some(prev) = prev {
+                    prev.wake();
+                }
+            }
+            // Being woken, so the value is ready.
+            Err(Self::WAKING) => waker.wake_by_ref(),
+            // Registered by another context at the same time, one of them is kept.
+            Err(_) => {}
+        }
+    }
+
+    /// Takes the registered waker, if it isn't being registered.
+    pub fn take(&self) -> Option<Waker> {
+        match self.state.fetch_or(Self::WAKING, AcqRel) {
+            Self::WAITING => {
+                let waker = self.waker.with_mut(|p| unsafe { (*p).take() });
+                self.state.fetch_and(!Self::WAKING, Release);
+                waker
+            }
+            // The registering context sees `WAKING` and wakes by itself.
+            _ => None,
+        }
+    }
+
+    /// Wakes the registered waker.
+    pub fn wake(&self) {
+        if let Some(waker) = self.take() {
+            waker.wake();
+        }
+    }
+}
+impl fmt::Debug for AtomicWaker {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("AtomicWaker")
+    }
+}
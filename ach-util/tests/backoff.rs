@@ -0,0 +1,91 @@
+use ach_util::*;
+use core::cell::Cell;
+
+fn busy(times: usize) -> impl FnMut(usize) -> Result<usize, Error<usize>> {
+    let mut left = times;
+    move |input| {
+        if left == 0 {
+            Ok(input)
+        } else {
+            left -= 1;
+            Err(Error {
+                kind: ErrorKind::Busy,
+                state: MemoryState::Initializing,
+                input,
+                retry: true,
+            })
+        }
+    }
+}
+
+#[test]
+fn retry_with_backoff() {
+    assert_eq!(retry(busy(3), 1).unwrap(), 1);
+    assert_eq!(retry_with(Exponential::new(), busy(10), 2).unwrap(), 2);
+    assert_eq!(retry_with(Bounded::new(Spin, 3), busy(3), 3).unwrap(), 3);
+
+    let err = retry_with(Bounded::new(Spin, 3), busy(4), 4).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::Timeout);
+    assert_eq!(err.state, MemoryState::Initializing);
+    assert_eq!(err.into_inner(), 4);
+
+    let waits = Cell::new(0);
+    let backoff = || {
+        waits.set(waits.get() + 1);
+        waits.get() < 5
+    };
+    assert!(retry_with(backoff, busy(10), 5).is_err());
+    assert_eq!(waits.get(), 5);
+
+    let err = retry_with(Spin, |input| Err::<(), _>(Error::new(input)), 6).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::Other);
+    assert_eq!(err.input, 6);
+}
+
+#[test]
+fn unwrap_with_backoff() {
+    let full = |times: usize| {
+        let mut left = times;
+        move |input: usize| {
+            if left == 0 {
+                Ok(input)
+            } else {
+                left -= 1;
+                Err(Error::full(input))
+            }
+        }
+    };
+    assert_eq!(unwrap(full(3), 1), 1);
+    assert_eq!(unwrap_with(Bounded::new(Spin, 3), full(3), 2).unwrap(), 2);
+
+    // Unlike `retry_with`, it retries the errors which aren't retryable.
+    assert_eq!(retry_with(Spin, full(1), 3).unwrap_err().kind, ErrorKind::Full);
+    let err = unwrap_with(Bounded::new(Spin, 3), full(4), 4).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::Timeout);
+    assert_eq!(err.into_inner(), 4);
+}
+
+struct Ticks(Cell<u32>);
+impl Clock for Ticks {
+    type Instant = u32;
+    fn now(&self) -> u32 {
+        let now = self.0.get();
+        self.0.set(now + 1);
+        now
+    }
+}
+
+#[test]
+fn deadline() {
+    let clock = Ticks(Cell::new(0));
+    assert_eq!(retry_with(Deadline::new(&clock, 5), busy(3), 1).unwrap(), 1);
+    assert_eq!(clock.0.get(), 3);
+
+    let err = retry_with(Deadline::new(&clock, 10), busy(10), 2).unwrap_err();
+    assert_eq!(err.input, 2);
+    assert_eq!(clock.0.get(), 11);
+
+    let backoff = Deadline::with_backoff(&clock, 20, Exponential::new());
+    assert!(retry_with(backoff.clone(), busy(3), 3).is_ok());
+    assert!(retry_with(backoff, busy(10), 4).is_err());
+}
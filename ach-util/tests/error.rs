@@ -0,0 +1,39 @@
+use ach_util::*;
+
+#[test]
+fn test() {
+    let err = Error::new(1);
+    assert_eq!(err.kind(), ErrorKind::Other);
+    assert_eq!(err.into_inner(), 1);
+
+    assert_eq!(
+        ErrorKind::from(MemoryState::Uninitialized),
+        ErrorKind::Empty
+    );
+    assert_eq!(
+        ErrorKind::from(MemoryState::Initialized),
+        ErrorKind::AlreadyInitialized
+    );
+    assert_eq!(ErrorKind::from(MemoryState::Erasing), ErrorKind::Busy);
+
+    let err = Error {
+        kind: ErrorKind::Full,
+        state: MemoryState::Initialized,
+        input: 2,
+        retry: false,
+    };
+    assert_eq!(format!("{}", err), "full");
+    assert_eq!(
+        format!("{:?}", err),
+        "Error { kind: Full, state: Initialized, retry: false }"
+    );
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn std_error() {
+    fn source(err: &dyn std::error::Error) -> String {
+        err.to_string()
+    }
+    assert_eq!(source(&Error::new(())), "other error");
+}
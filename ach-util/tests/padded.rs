@@ -0,0 +1,38 @@
+use ach_util::CachePadded;
+use core::mem;
+use core::sync::atomic::AtomicUsize;
+
+#[test]
+fn layout() {
+    #[repr(C)]
+    struct Indexes {
+        start: CachePadded<AtomicUsize>,
+        end: CachePadded<AtomicUsize>,
+    }
+    let distance = mem::offset_of!(Indexes, end) - mem::offset_of!(Indexes, start);
+    #[cfg(feature = "cache-padded")]
+    {
+        let line = if cfg!(any(target_arch = "x86_64", target_arch = "aarch64")) {
+            128
+        } else {
+            64
+        };
+        assert_eq!(mem::align_of::<CachePadded<AtomicUsize>>(), line);
+        assert_eq!(mem::size_of::<CachePadded<AtomicUsize>>(), line);
+        assert_eq!(distance, line);
+    }
+    #[cfg(not(feature = "cache-padded"))]
+    {
+        assert_eq!(
+            mem::align_of::<CachePadded<AtomicUsize>>(),
+            mem::align_of::<AtomicUsize>()
+        );
+        assert_eq!(
+            mem::size_of::<CachePadded<AtomicUsize>>(),
+            mem::size_of::<usize>()
+        );
+        assert_eq!(distance, mem::size_of::<usize>());
+    }
+    let end = CachePadded::new(AtomicUsize::new(1));
+    assert_eq!(end.into_inner().into_inner(), 1);
+}
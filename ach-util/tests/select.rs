@@ -0,0 +1,186 @@
+use ach_util::*;
+use std::collections::VecDeque;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll, Wake, Waker};
+
+struct Deque(VecDeque<usize>);
+impl Consumer<usize> for Deque {
+    fn try_pop(&mut self) -> Result<usize, Error<()>> {
+        self.0.pop_front().ok_or_else(Error::empty)
+    }
+}
+/// Never ready, but always retryable.
+struct Busy;
+impl Consumer<usize> for Busy {
+    fn try_pop(&mut self) -> Result<usize, Error<()>> {
+        Err(Error {
+            kind: ErrorKind::Busy,
+            state: MemoryState::Erasing,
+            input: (),
+            retry: true,
+        })
+    }
+}
+
+#[test]
+fn fair() {
+    let mut a = Deque((0..10).collect());
+    let mut b = Deque((10..13).collect());
+    let mut sel = Selector::<usize, 2>::new().with(&mut a).with(&mut b);
+    assert_eq!(sel.len(), 2);
+    // The busy `a` doesn't starve `b`.
+    let got: Vec<_> = (0..6).map(|_| sel.try_select().unwrap()).collect();
+    assert_eq!(got, [(0, 0), (1, 10), (0, 1), (1, 11), (0, 2), (1, 12)]);
+    assert_eq!(sel.try_select().unwrap(), (0, 3));
+    assert_eq!(sel.try_select().unwrap(), (0, 4));
+}
+
+#[test]
+fn empty() {
+    let mut a = Deque(VecDeque::new());
+    let mut b = Busy;
+    let mut sel = Selector::<usize, 2>::new();
+    assert_eq!(sel.add(&mut a).ok(), Some(0));
+    let err = sel.try_select().unwrap_err();
+    assert_eq!(err.kind, ErrorKind::Empty);
+    assert!(!err.retry);
+    assert_eq!(sel.add(&mut b).ok(), Some(1));
+    assert!(sel.try_select().unwrap_err().retry);
+
+    let mut c = Busy;
+    assert!(sel.add(&mut c).is_err());
+    assert_eq!(sel.select_for(3).unwrap_err().kind, ErrorKind::Timeout);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn timeout() {
+    let mut a = Deque(VecDeque::new());
+    let mut sel = Selector::<usize, 1>::new().with(&mut a);
+    let err = sel
+        .select_timeout(std::time::Duration::from_millis(10))
+        .unwrap_err();
+    assert_eq!(err.kind, ErrorKind::Timeout);
+}
+
+struct CountWake(std::sync::atomic::AtomicUsize);
+impl Wake for CountWake {
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref()
+    }
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.0.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+    }
+}
+
+#[test]
+fn future() {
+    let wake = Arc::new(CountWake(Default::default()));
+    let waker = Waker::from(wake.clone());
+    let mut cx = Context::from_waker(&waker);
+
+    let mut a = Deque(VecDeque::new());
+    {
+        let mut sel = Selector::<usize, 1>::new().with(&mut a);
+        let mut fut = sel.select_async();
+        // Pending wakes itself, so the executor polls it again.
+        assert!(Pin::new(&mut fut).poll(&mut cx).is_pending());
+        assert_eq!(wake.0.load(std::sync::atomic::Ordering::SeqCst), 1);
+    }
+    a.0.push_back(7);
+    let mut sel = Selector::<usize, 1>::new().with(&mut a);
+    let mut fut = sel.select_async();
+    assert_eq!(Pin::new(&mut fut).poll(&mut cx), Poll::Ready((0, 7)));
+}
+
+/// Wakes the registered waker when a value is pushed.
+#[derive(Default)]
+struct Notify {
+    vals: VecDeque<usize>,
+    waker: Option<Waker>,
+}
+impl Notify {
+    fn push(&mut self, val: usize) {
+        self.vals.push_back(val);
+        if let Some(waker) = self.waker.take() {
+            waker.wake();
+        }
+    }
+}
+impl Consumer<usize> for Notify {
+    fn try_pop(&mut self) -> Result<usize, Error<()>> {
+        self.vals.pop_front().ok_or_else(Error::empty)
+    }
+    fn register_waker(&mut self, waker: &Waker) -> bool {
+        self.waker = Some(waker.clone());
+        true
+    }
+}
+
+#[test]
+fn future_waker() {
+    let wake = Arc::new(CountWake(Default::default()));
+    let waker = Waker::from(wake.clone());
+    let mut cx = Context::from_waker(&waker);
+
+    let mut a = Notify::default();
+    let mut b = Notify::default();
+    {
+        let mut sel = Selector::<usize, 2>::new().with(&mut a).with(&mut b);
+        let mut fut = sel.select_async();
+        // Every consumer registered the waker, so it doesn't wake itself.
+        assert!(Pin::new(&mut fut).poll(&mut cx).is_pending());
+        assert_eq!(wake.0.load(std::sync::atomic::Ordering::SeqCst), 0);
+    }
+    b.push(8);
+    assert_eq!(wake.0.load(std::sync::atomic::Ordering::SeqCst), 1);
+    assert!(a.waker.is_some());
+
+    // One consumer without support makes it busy-poll.
+    let mut c = Deque(VecDeque::new());
+    let mut sel = Selector::<usize, 3>::new()
+        .with(&mut a)
+        .with(&mut b)
+        .with(&mut c);
+    let mut fut = sel.select_async();
+    assert_eq!(Pin::new(&mut fut).poll(&mut cx), Poll::Ready((1, 8)));
+    let mut fut = sel.select_async();
+    assert!(Pin::new(&mut fut).poll(&mut cx).is_pending());
+    assert_eq!(wake.0.load(std::sync::atomic::Ordering::SeqCst), 2);
+}
+
+/// `Notify` shared with a producer thread, which counts the pops.
+#[cfg(feature = "std")]
+#[derive(Clone, Default)]
+struct Shared(Arc<std::sync::Mutex<(Notify, usize)>>);
+#[cfg(feature = "std")]
+impl Consumer<usize> for Shared {
+    fn try_pop(&mut self) -> Result<usize, Error<()>> {
+        let mut inner = self.0.lock().unwrap();
+        inner.1 += 1;
+        inner.0.try_pop()
+    }
+    fn register_waker(&mut self, waker: &Waker) -> bool {
+        self.0.lock().unwrap().0.register_waker(waker)
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn park() {
+    let mut a = Shared::default();
+    let pusher = {
+        let a = a.clone();
+        std::thread::spawn(move || {
+            std::thread::sleep(std::time::Duration::from_millis(50));
+            a.0.lock().unwrap().0.push(9);
+        })
+    };
+    let mut sel = Selector::<usize, 1>::new().with(&mut a);
+    assert_eq!(sel.select(), (0, 9));
+    pusher.join().unwrap();
+    // Parked until the push instead of polling all the time.
+    assert!(a.0.lock().unwrap().1 < 10);
+}
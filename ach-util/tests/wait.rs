@@ -0,0 +1,37 @@
+use ach_util::*;
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+fn busy(times: usize) -> impl FnMut(usize) -> Result<usize, Error<usize>> {
+    let mut left = times;
+    move |input| {
+        if left == 0 {
+            Ok(input)
+        } else {
+            left -= 1;
+            Err(Error {
+                kind: ErrorKind::Busy,
+                state: MemoryState::Initializing,
+                input,
+                retry: true,
+            })
+        }
+    }
+}
+
+#[test]
+fn test() {
+    static WAITS: AtomicUsize = AtomicUsize::new(0);
+    fn wait() {
+        WAITS.fetch_add(1, Ordering::Relaxed);
+    }
+    spin_loop::set_wait(wait);
+    assert_eq!(retry(busy(3), 1).unwrap(), 1);
+    assert_eq!(WAITS.load(Ordering::Relaxed), 3);
+
+    let mut backoff = Exponential::with_limit(2);
+    for _ in 0..4 {
+        assert!(backoff.snooze());
+    }
+    assert_eq!(WAITS.load(Ordering::Relaxed), 5);
+    spin_loop::reset_wait();
+}
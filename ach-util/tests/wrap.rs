@@ -0,0 +1,92 @@
+use ach_util::{MemoryOp, MemoryRefer, MemoryRing, MemoryState, Op, StateWord};
+
+#[test]
+fn ring_cycle() {
+    let max = MemoryRing::max_cycle();
+    #[cfg(not(feature = "wide-state"))]
+    assert_eq!(max, 1 << 24);
+    #[cfg(all(feature = "wide-state", target_pointer_width = "64"))]
+    assert_eq!(max, 1 << 32);
+
+    let last = MemoryRing::new(max - 1, MemoryState::Erasing);
+    assert_eq!(last.cycle(), max - 1);
+    assert!(last.state().is_erasing());
+    let first = last.next();
+    assert_eq!(first, MemoryRing::new(max, MemoryState::Uninitialized));
+    assert_eq!(first.cycle(), 0);
+    assert!(first.state().is_uninitialized());
+    assert_eq!(StateWord::from(MemoryRing::INIT), 0);
+
+    // The cycles near the wrap boundary are ordered across it.
+    for (a, b) in [(max - 1, 0), (max - 2, 1), (max / 8 * 7, max / 8)] {
+        let old = MemoryRing::new(a, MemoryState::Initialized);
+        let new = MemoryRing::new(b, MemoryState::Uninitialized);
+        assert!(old < new);
+        assert!(new > old);
+    }
+    // Not across it in the middle of the window.
+    let a = MemoryRing::new(max / 2 - 1, MemoryState::Erasing);
+    let b = MemoryRing::new(max / 2, MemoryState::Uninitialized);
+    assert!(a < b);
+    assert!(b.next() > b);
+}
+
+#[test]
+fn ring_idx() {
+    for size in [1, 3, 100, 1024] {
+        let max_idx = MemoryRing::max_idx(size);
+        assert!(max_idx > 0);
+        assert_eq!(max_idx % size, 0);
+        assert_eq!(max_idx % MemoryRing::max_cycle(), 0);
+        // The cycle of the last index is the last one, so the next index of cycle 0 follows it.
+        let cycle = MemoryRing::cycle_of_idx(max_idx - 1, size);
+        let last = MemoryRing::new(cycle, MemoryState::Erasing);
+        assert_eq!(last.cycle(), MemoryRing::max_cycle() - 1);
+        assert_eq!(
+            last.next(),
+            MemoryRing::new(
+                MemoryRing::cycle_of_idx(0, size),
+                MemoryState::Uninitialized
+            )
+        );
+    }
+}
+
+#[test]
+fn refer_max() {
+    let max = MemoryRefer::max_refer();
+    #[cfg(not(feature = "wide-state"))]
+    assert_eq!(max, (1 << 24) - 1);
+    #[cfg(all(feature = "wide-state", target_pointer_width = "64"))]
+    assert_eq!(max, (1 << 56) - 1);
+
+    let mut refer = MemoryRefer::from(MemoryState::Initialized);
+    refer = MemoryRefer::from(StateWord::from(refer) + max as StateWord - 1);
+    assert_eq!(refer.ref_num(), Ok(max - 1));
+    refer.ref_add().unwrap();
+    assert_eq!(refer.ref_num(), Ok(max));
+    // Saturated, it never overflows into the state.
+    refer.ref_add().unwrap();
+    refer.ref_sub().unwrap();
+    assert_eq!(refer.ref_num(), Ok(max));
+    assert!(refer.state().is_initialized());
+}
+
+#[test]
+fn op_version() {
+    let bits = (StateWord::BITS - 8) / 2;
+    let mut op = MemoryOp::from(((1 << bits) - 1) << (8 + bits));
+    op.finish();
+    assert_eq!(op.cur_version() as u64, (1 << bits) - 1);
+    assert!(op.is_finished());
+
+    // The next version wraps to 0, and doesn't touch the current one.
+    assert_eq!(op.set_op(Op::Write), 0);
+    assert_eq!(op.op(), Op::Write);
+    assert_eq!(op.cur_version() as u64, (1 << bits) - 1);
+    assert!(!op.is_finished());
+    op.finish();
+    assert_eq!(op.cur_version(), 0);
+    assert_eq!(op.op(), Op::None);
+    assert!(op.is_finished());
+}
@@ -0,0 +1,17 @@
+use std::env;
+
+fn main() {
+    let target = env::var("TARGET").unwrap();
+
+    if env::var_os("CARGO_FEATURE_CRITICAL_SECTION").is_some()
+        && env::var_os("CARGO_FEATURE_CRITICAL_SECTION_IMPL").is_none()
+    {
+        println!("cargo:rustc-cfg=cs_delegate");
+    }
+    if target.starts_with("thumbv7m-")
+        || target.starts_with("thumbv7em-")
+        || target.starts_with("thumbv8m.main")
+    {
+        println!("cargo:rustc-cfg=has_basepri");
+    }
+}
@@ -0,0 +1,25 @@
+use crate::global::Global;
+
+/// Interrupt controller registered at runtime by `set_backend`.
+pub trait Backend: Sync {
+    /// Get specific interrupts and returns the current setting
+    fn get_mask(&self) -> u32;
+    /// Set specific interrupts
+    /// # Safety
+    unsafe fn set_mask(&self, mask: u32);
+}
+
+static BACKEND: Global<dyn Backend> = Global::new();
+
+/// Registers the interrupt controller, which replaces the built-in target.
+///
+/// The backend can only be set once, call it at startup before entering any critical section.
+/// Returns `Err(backend)` if a backend is already set.
+pub fn set_backend(backend: &'static dyn Backend) -> Result<(), &'static dyn Backend> {
+    BACKEND.set(backend)
+}
+
+/// Returns the registered backend, `None` if the built-in target is used.
+pub fn backend() -> Option<&'static dyn Backend> {
+    BACKEND.get()
+}
@@ -1,27 +1,183 @@
-use crate::{disable_mask, set_mask, MASK_ALL};
+#[cfg(feature = "latency")]
+use crate::latency;
+#[cfg(all(feature = "multicore", not(cs_delegate)))]
+use crate::multicore;
+#[cfg(not(cs_delegate))]
+use crate::target::{acquire, release};
+use crate::MASK_ALL;
+#[cfg(not(cs_delegate))]
+use crate::{disable_mask, set_mask};
+#[cfg(not(target_os = "none"))]
+use core::cell::Cell;
 use core::ops::Deref;
+#[cfg(feature = "latency")]
+use core::panic::Location;
+#[cfg(target_pointer_width = "16")]
+use core::sync::atomic::AtomicU16;
+#[cfg(not(target_pointer_width = "16"))]
+use core::sync::atomic::AtomicU32;
+#[cfg(target_os = "none")]
+use core::sync::atomic::AtomicUsize;
+use core::sync::atomic::Ordering::Relaxed;
+
+#[cfg(target_pointer_width = "16")]
+static DEFAULT_MASK: AtomicU16 = AtomicU16::new(MASK_ALL as u16);
+#[cfg(not(target_pointer_width = "16"))]
+static DEFAULT_MASK: AtomicU32 = AtomicU32::new(MASK_ALL);
+
+#[cfg(target_os = "none")]
+static DEPTH: AtomicUsize = AtomicUsize::new(0);
+#[cfg(all(not(target_os = "none"), not(loom)))]
+std::thread_local! {
+    static DEPTH: Cell<usize> = const { Cell::new(0) };
+}
+// The threads of loom share one OS thread.
+#[cfg(all(not(target_os = "none"), loom))]
+loom::thread_local! {
+    static DEPTH: Cell<usize> = Cell::new(0);
+}
+
+// Nested critical sections always exit in reverse order, so the interrupts
+// preempting between load and store restore the depth before returning.
+#[cfg(target_os = "none")]
+#[inline]
+fn set_depth(f: impl FnOnce(usize) -> usize) {
+    DEPTH.store(f(DEPTH.load(Relaxed)), Relaxed);
+}
+#[cfg(not(target_os = "none"))]
+#[inline]
+fn set_depth(f: impl FnOnce(usize) -> usize) {
+    DEPTH.with(|x| x.set(f(x.get())));
+}
+
+/// Returns the nesting depth of critical sections on the current core, `0` if not in critical section.
+#[cfg(target_os = "none")]
+#[inline]
+pub fn nesting_depth() -> usize {
+    #[cfg(all(feature = "multicore", not(cs_delegate)))]
+    if !multicore::is_owner() {
+        return 0;
+    }
+    DEPTH.load(Relaxed)
+}
+/// Returns the nesting depth of critical sections on the current thread, `0` if not in critical section.
+#[cfg(not(target_os = "none"))]
+#[inline]
+pub fn nesting_depth() -> usize {
+    DEPTH.with(|x| x.get())
+}
+
+/// Returns true if the current code is executing within a critical section.
+///
+/// The interrupts left enabled by `CriticalSection::with_mask` also see it.
+#[inline]
+pub fn in_critical_section() -> bool {
+    nesting_depth() != 0
+}
+
+/// Returns the interrupts which are disabled by `CriticalSection::new`.
+#[inline]
+#[allow(clippy::unnecessary_cast)]
+pub fn default_mask() -> u32 {
+    DEFAULT_MASK.load(Relaxed) as u32
+}
+
+/// Sets the interrupts which are disabled by `CriticalSection::new`, and returns the previous setting.
+///
+/// All ach containers enter critical section by `CriticalSection::new`, so it can keep the
+/// high priority interrupts which never touch them enabled. Default is `MASK_ALL`.
+///
+/// # Safety
+/// Every interrupt which is not in `mask` must never access the data protected by `CriticalSection::new`.
+#[inline]
+pub unsafe fn set_default_mask(mask: u32) -> u32 {
+    let prev = default_mask();
+    DEFAULT_MASK.store(mask as _, Relaxed);
+    prev
+}
 
 /// Critical section token.
 ///
 /// The current core is executing code within a critical section.
 pub struct CriticalSection<'cs> {
     inner: bare_metal::CriticalSection<'cs>,
+    #[cfg(not(cs_delegate))]
     mask: u32,
+    #[cfg(cs_delegate)]
+    restore: ::critical_section::RestoreState,
+    #[cfg(feature = "latency")]
+    latency: Option<(&'static Location<'static>, u64)>,
 }
 impl<'cs> Default for CriticalSection<'cs> {
+    #[cfg_attr(feature = "latency", track_caller)]
     fn default() -> Self {
         Self::new()
     }
 }
 impl<'cs> CriticalSection<'cs> {
     /// Into a critical section. And exit the critical section, when it drop.
+    ///
+    /// It disables the interrupts in `default_mask()`.
+    #[cfg_attr(feature = "latency", track_caller)]
     pub fn new() -> Self {
-        let mask = disable_mask(MASK_ALL);
+        unsafe { Self::with_mask(default_mask()) }
+    }
+    /// Into a critical section which only disables the interrupts in `mask`.
+    /// And restore the previous setting, when it drop.
+    ///
+    /// With the `critical-section` feature, it enters the critical section of the
+    /// registered provider and ignores `mask`.
+    ///
+    /// # Safety
+    /// Every interrupt which is not in `mask` must never access the data protected by this critical section.
+    #[cfg(not(cs_delegate))]
+    #[cfg_attr(feature = "latency", track_caller)]
+    pub unsafe fn with_mask(mask: u32) -> Self {
+        #[cfg(feature = "latency")]
+        let location = Location::caller();
+        acquire();
+        let mask = disable_mask(mask);
+        #[cfg(feature = "multicore")]
+        multicore::lock();
+        set_depth(|x| x + 1);
         Self {
-            inner: unsafe { bare_metal::CriticalSection::new() },
+            inner: bare_metal::CriticalSection::new(),
             mask,
+            #[cfg(feature = "latency")]
+            latency: latency::now().map(|t| (location, t)),
         }
     }
+    /// Into a critical section which only disables the interrupts in `mask`.
+    /// And restore the previous setting, when it drop.
+    ///
+    /// With the `critical-section` feature, it enters the critical section of the
+    /// registered provider and ignores `mask`.
+    ///
+    /// # Safety
+    /// Every interrupt which is not in `mask` must never access the data protected by this critical section.
+    #[cfg(cs_delegate)]
+    #[cfg_attr(feature = "latency", track_caller)]
+    pub unsafe fn with_mask(_mask: u32) -> Self {
+        #[cfg(feature = "latency")]
+        let location = Location::caller();
+        let restore = ::critical_section::acquire();
+        set_depth(|x| x + 1);
+        Self {
+            inner: bare_metal::CriticalSection::new(),
+            restore,
+            #[cfg(feature = "latency")]
+            latency: latency::now().map(|t| (location, t)),
+        }
+    }
+    /// Returns the token of `critical-section` crate.
+    #[cfg(any(feature = "critical-section", feature = "critical-section-impl"))]
+    #[cfg_attr(
+        docsrs,
+        doc(cfg(any(feature = "critical-section", feature = "critical-section-impl")))
+    )]
+    pub fn token(&self) -> ::critical_section::CriticalSection<'_> {
+        unsafe { ::critical_section::CriticalSection::new() }
+    }
 }
 impl<'cs> Deref for CriticalSection<'cs> {
     type Target = bare_metal::CriticalSection<'cs>;
@@ -30,7 +186,49 @@ impl<'cs> Deref for CriticalSection<'cs> {
     }
 }
 impl<'cs> Drop for CriticalSection<'cs> {
+    #[cfg(not(cs_delegate))]
     fn drop(&mut self) {
+        #[cfg(feature = "latency")]
+        if let (Some((location, start)), Some(end)) = (self.latency, latency::now()) {
+            latency::record(location, end.wrapping_sub(start));
+        }
+        set_depth(|x| x - 1);
+        #[cfg(feature = "multicore")]
+        multicore::unlock();
         unsafe { set_mask(self.mask) };
+        release();
+    }
+    #[cfg(cs_delegate)]
+    fn drop(&mut self) {
+        #[cfg(feature = "latency")]
+        if let (Some((location, start)), Some(end)) = (self.latency, latency::now()) {
+            latency::record(location, end.wrapping_sub(start));
+        }
+        set_depth(|x| x - 1);
+        unsafe { ::critical_section::release(self.restore) };
+    }
+}
+
+/// The `critical-section` provider, which enters critical section like `CriticalSection::new`.
+#[cfg(feature = "critical-section-impl")]
+struct InterruptCriticalSection;
+#[cfg(feature = "critical-section-impl")]
+::critical_section::set_impl!(InterruptCriticalSection);
+#[cfg(feature = "critical-section-impl")]
+unsafe impl ::critical_section::Impl for InterruptCriticalSection {
+    unsafe fn acquire() -> ::critical_section::RawRestoreState {
+        acquire();
+        let mask = disable_mask(default_mask());
+        #[cfg(feature = "multicore")]
+        multicore::lock();
+        set_depth(|x| x + 1);
+        mask
+    }
+    unsafe fn release(mask: ::critical_section::RawRestoreState) {
+        set_depth(|x| x - 1);
+        #[cfg(feature = "multicore")]
+        multicore::unlock();
+        set_mask(mask);
+        release();
     }
 }
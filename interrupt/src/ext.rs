@@ -34,6 +34,7 @@ pub unsafe fn enable() -> u32 {
 ///
 /// This as also known as a "critical section".
 #[inline]
+#[cfg_attr(feature = "latency", track_caller)]
 pub fn free<F, R>(f: F) -> R
 where
     F: FnOnce(&bare_metal::CriticalSection) -> R,
@@ -0,0 +1,58 @@
+use core::cell::UnsafeCell;
+use core::sync::atomic::{
+    AtomicU8,
+    Ordering::{Acquire, Release},
+};
+
+const UNINIT: u8 = 0;
+const INITIALIZING: u8 = 1;
+const INITIALIZED: u8 = 2;
+
+/// Reference which can only be set once, used by the runtime registrations.
+pub(crate) struct Global<T: ?Sized + 'static> {
+    state: AtomicU8,
+    val: UnsafeCell<Option<&'static T>>,
+}
+unsafe impl<T: ?Sized + Sync> Sync for Global<T> {}
+impl<T: ?Sized + 'static> Global<T> {
+    pub const fn new() -> Self {
+        Self {
+            state: AtomicU8::new(UNINIT),
+            val: UnsafeCell::new(None),
+        }
+    }
+    /// Returns `Err(val)` if it is already set.
+    pub fn set(&self, val: &'static T) -> Result<(), &'static T> {
+        #[cfg(target_has_atomic = "8")]
+        if self
+            .state
+            .compare_exchange(UNINIT, INITIALIZING, Acquire, Acquire)
+            .is_err()
+        {
+            return Err(val);
+        }
+        #[cfg(not(target_has_atomic = "8"))]
+        {
+            let prev = crate::disable();
+            let state = self.state.load(Acquire);
+            if state == UNINIT {
+                self.state.store(INITIALIZING, Release);
+            }
+            unsafe { crate::set_mask(prev) };
+            if state != UNINIT {
+                return Err(val);
+            }
+        }
+        unsafe { *self.val.get() = Some(val) };
+        self.state.store(INITIALIZED, Release);
+        Ok(())
+    }
+    #[inline]
+    pub fn get(&self) -> Option<&'static T> {
+        if self.state.load(Acquire) == INITIALIZED {
+            unsafe { *self.val.get() }
+        } else {
+            None
+        }
+    }
+}
@@ -0,0 +1,116 @@
+use crate::global::Global;
+use core::panic::Location;
+
+/// Timestamp source for measuring critical sections, such as a cycle counter or `Instant`.
+pub trait Clock: Sync {
+    /// Returns the current time in ticks.
+    fn now(&self) -> u64;
+}
+
+static CLOCK: Global<dyn Clock> = Global::new();
+
+/// Registers the clock, and starts measuring critical sections.
+///
+/// The clock can only be set once. Returns `Err(clock)` if a clock is already set.
+pub fn set_clock(clock: &'static dyn Clock) -> Result<(), &'static dyn Clock> {
+    CLOCK.set(clock)
+}
+
+#[inline]
+pub(crate) fn now() -> Option<u64> {
+    CLOCK.get().map(|clock| clock.now())
+}
+
+/// Statistics of the critical sections entered at a call site.
+#[derive(Debug, Clone, Copy)]
+pub struct LatencyStats {
+    /// Where the critical section is entered.
+    pub location: &'static Location<'static>,
+    /// Number of critical sections.
+    pub count: u64,
+    /// Maximum ticks in critical section.
+    pub max: u64,
+    /// Total ticks in critical section.
+    pub total: u64,
+}
+
+/// Maximum number of recorded call sites, the others are ignored.
+pub const LATENCY_SITES: usize = 32;
+
+struct Sites([Option<LatencyStats>; LATENCY_SITES]);
+impl Sites {
+    const NONE: Option<LatencyStats> = None;
+    const fn new() -> Self {
+        Self([Self::NONE; LATENCY_SITES])
+    }
+}
+
+#[cfg(target_os = "none")]
+static mut SITES: Sites = Sites::new();
+#[cfg(target_os = "none")]
+fn with_sites<R>(f: impl FnOnce(&mut Sites) -> R) -> R {
+    // Keeps the interrupts left enabled by `with_mask` from recording at the same time.
+    let prev = crate::disable();
+    let ret = f(unsafe { &mut *core::ptr::addr_of_mut!(SITES) });
+    unsafe { crate::set_mask(prev) };
+    ret
+}
+#[cfg(not(target_os = "none"))]
+static SITES: std::sync::Mutex<Sites> = std::sync::Mutex::new(Sites::new());
+#[cfg(not(target_os = "none"))]
+fn with_sites<R>(f: impl FnOnce(&mut Sites) -> R) -> R {
+    let mut sites = SITES.lock().unwrap_or_else(|e| e.into_inner());
+    f(&mut sites)
+}
+
+pub(crate) fn record(location: &'static Location<'static>, ticks: u64) {
+    with_sites(|sites| {
+        for site in sites.0.iter_mut() {
+            match site {
+                Some(stats) if stats.location == location => {
+                    stats.count += 1;
+                    stats.max = stats.max.max(ticks);
+                    stats.total += ticks;
+                    return;
+                }
+                Some(_) => {}
+                None => {
+                    *site = Some(LatencyStats {
+                        location,
+                        count: 1,
+                        max: ticks,
+                        total: ticks,
+                    });
+                    return;
+                }
+            }
+        }
+    })
+}
+
+/// Calls `f` with the statistics of every call site.
+pub fn for_each_latency(mut f: impl FnMut(&LatencyStats)) {
+    for i in 0..LATENCY_SITES {
+        match with_sites(|sites| sites.0[i]) {
+            Some(stats) => f(&stats),
+            None => return,
+        }
+    }
+}
+
+/// Returns the statistics of the call site at `location`.
+pub fn latency_of(location: &Location<'_>) -> Option<LatencyStats> {
+    with_sites(|sites| {
+        sites
+            .0
+            .iter()
+            .flatten()
+            .find(|stats| stats.location == location)
+            .copied()
+    })
+}
+
+/// Clears the statistics of all call sites.
+pub fn reset_latency() {
+    with_sites(|sites| *sites = Sites::new())
+}
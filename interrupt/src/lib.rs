@@ -1,13 +1,48 @@
 #![no_std]
 #![cfg_attr(docsrs, feature(doc_cfg))]
+#[cfg(not(target_os = "none"))]
+extern crate std;
 
+#[cfg(feature = "backend")]
+mod backend;
 mod critical_section;
 mod ext;
+#[cfg(any(
+    feature = "backend",
+    feature = "latency",
+    all(feature = "multicore", not(cs_delegate))
+))]
+mod global;
+#[cfg(feature = "latency")]
+mod latency;
+#[cfg(all(feature = "multicore", not(cs_delegate)))]
+mod multicore;
+mod mutex;
 mod target;
 
+#[cfg(feature = "backend")]
+#[cfg_attr(docsrs, doc(cfg(feature = "backend")))]
+pub use backend::{backend, set_backend, Backend};
 pub use bare_metal::Mutex;
-pub use critical_section::CriticalSection;
+pub use critical_section::{
+    default_mask, in_critical_section, nesting_depth, set_default_mask, CriticalSection,
+};
 pub use ext::*;
+#[cfg(feature = "latency")]
+#[cfg_attr(docsrs, doc(cfg(feature = "latency")))]
+pub use latency::{
+    for_each_latency, latency_of, reset_latency, set_clock, Clock, LatencyStats, LATENCY_SITES,
+};
+#[cfg(all(feature = "multicore", not(cs_delegate), target_has_atomic = "8"))]
+#[cfg_attr(docsrs, doc(cfg(feature = "multicore")))]
+pub use multicore::AtomicCoreLock;
+#[cfg(all(feature = "multicore", not(cs_delegate)))]
+#[cfg_attr(docsrs, doc(cfg(feature = "multicore")))]
+pub use multicore::{set_core_lock, CoreLock};
+pub use mutex::RefCellMutex;
+#[cfg(all(feature = "sim", not(feature = "custom"), not(target_os = "none")))]
+#[cfg_attr(docsrs, doc(cfg(feature = "sim")))]
+pub use target::sim;
 pub use target::{get_mask, set_mask};
 
 pub const MASK_ALL: u32 = u32::MAX;
@@ -0,0 +1,100 @@
+use crate::global::Global;
+#[cfg(target_has_atomic = "8")]
+use core::sync::atomic::{
+    AtomicBool,
+    Ordering::{Acquire, Release},
+};
+use core::sync::atomic::{AtomicUsize, Ordering::Relaxed};
+
+/// Lock shared by all cores, such as a hardware spinlock.
+///
+/// Critical sections take it after masking the interrupts of local core, so it doesn't need to be reentrant.
+pub trait CoreLock: Sync {
+    /// Returns the id of current core.
+    fn core_id(&self) -> usize;
+    /// Takes the lock, spinning until it is released by other cores.
+    fn lock(&self);
+    /// Releases the lock.
+    fn unlock(&self);
+}
+
+/// Atomic spinlock for targets which don't have a hardware spinlock.
+#[cfg(target_has_atomic = "8")]
+pub struct AtomicCoreLock {
+    core_id: fn() -> usize,
+    locked: AtomicBool,
+}
+#[cfg(target_has_atomic = "8")]
+impl AtomicCoreLock {
+    /// `core_id` returns the id of current core.
+    pub const fn new(core_id: fn() -> usize) -> Self {
+        Self {
+            core_id,
+            locked: AtomicBool::new(false),
+        }
+    }
+}
+#[cfg(target_has_atomic = "8")]
+impl CoreLock for AtomicCoreLock {
+    fn core_id(&self) -> usize {
+        (self.core_id)()
+    }
+    fn lock(&self) {
+        while self
+            .locked
+            .compare_exchange_weak(false, true, Acquire, Relaxed)
+            .is_err()
+        {
+            spin_loop::spin();
+        }
+    }
+    fn unlock(&self) {
+        self.locked.store(false, Release);
+    }
+}
+
+static CORE_LOCK: Global<dyn CoreLock> = Global::new();
+const NO_OWNER: usize = usize::MAX;
+/// Core which owns the lock.
+static OWNER: AtomicUsize = AtomicUsize::new(NO_OWNER);
+/// Nesting depth of the owner, only changed by the owner.
+static DEPTH: AtomicUsize = AtomicUsize::new(0);
+
+/// Registers the lock, and every critical section excludes the other cores from then on.
+///
+/// The lock can only be set once, call it at startup before entering any critical section.
+/// Returns `Err(lock)` if a lock is already set.
+pub fn set_core_lock(lock: &'static dyn CoreLock) -> Result<(), &'static dyn CoreLock> {
+    CORE_LOCK.set(lock)
+}
+
+/// Returns true if the current core owns the lock of critical section.
+#[cfg(target_os = "none")]
+pub(crate) fn is_owner() -> bool {
+    match CORE_LOCK.get() {
+        Some(lock) => OWNER.load(Relaxed) == lock.core_id(),
+        None => true,
+    }
+}
+
+pub(crate) fn lock() {
+    if let Some(lock) = CORE_LOCK.get() {
+        let id = lock.core_id();
+        if OWNER.load(Relaxed) != id {
+            lock.lock();
+            OWNER.store(id, Relaxed);
+        }
+        DEPTH.store(DEPTH.load(Relaxed) + 1, Relaxed);
+    }
+}
+
+pub(crate) fn unlock() {
+    if let Some(lock) = CORE_LOCK.get() {
+        let depth = DEPTH.load(Relaxed) - 1;
+        DEPTH.store(depth, Relaxed);
+        if depth == 0 {
+            OWNER.store(NO_OWNER, Relaxed);
+            lock.unlock();
+        }
+    }
+}
@@ -0,0 +1,18 @@
+use bare_metal::{CriticalSection, Mutex};
+use core::cell::{Ref, RefCell, RefMut};
+
+/// Borrows the data in `Mutex<RefCell<T>>` within critical section.
+pub trait RefCellMutex<T> {
+    /// Immutably borrows the data, panics if it is mutably borrowed.
+    fn borrow_ref<'cs>(&'cs self, cs: CriticalSection<'cs>) -> Ref<'cs, T>;
+    /// Mutably borrows the data, panics if it is borrowed.
+    fn borrow_ref_mut<'cs>(&'cs self, cs: CriticalSection<'cs>) -> RefMut<'cs, T>;
+}
+impl<T> RefCellMutex<T> for Mutex<RefCell<T>> {
+    fn borrow_ref<'cs>(&'cs self, cs: CriticalSection<'cs>) -> Ref<'cs, T> {
+        self.borrow(cs).borrow()
+    }
+    fn borrow_ref_mut<'cs>(&'cs self, cs: CriticalSection<'cs>) -> RefMut<'cs, T> {
+        self.borrow(cs).borrow_mut()
+    }
+}
@@ -0,0 +1,80 @@
+use cortex_m::interrupt;
+use cortex_m::register::primask;
+
+/// Get specific interrupts and returns the current setting
+///
+/// Without BASEPRI, it is `1` if interrupts are enabled, otherwise `0`.
+#[cfg(not(has_basepri))]
+#[inline]
+pub fn get_mask() -> u32 {
+    primask::read().is_active() as u32
+}
+
+/// Set specific interrupts
+/// # Safety
+#[cfg(not(has_basepri))]
+#[inline]
+pub unsafe fn set_mask(mask: u32) {
+    if mask == 0 {
+        interrupt::disable();
+    } else {
+        interrupt::enable();
+    }
+}
+
+/// Bits of priority levels in mask.
+///
+/// Bit `n` enables the interrupts whose priority is `n << 5`,
+/// so it works on the devices which implement at least 3 priority bits.
+#[cfg(has_basepri)]
+const LEVELS: u32 = 0xFF;
+#[cfg(has_basepri)]
+const LEVEL_SHIFT: u32 = 5;
+
+/// Get specific interrupts and returns the current setting
+///
+/// With BASEPRI, the low 8 bits are the enabled priority levels (bit `n` is the level `n << 5`),
+/// and the other bits are set if PRIMASK doesn't mask all interrupts.
+#[cfg(has_basepri)]
+#[inline]
+pub fn get_mask() -> u32 {
+    use cortex_m::register::basepri;
+
+    if !primask::read().is_active() {
+        return 0;
+    }
+    let levels = match basepri::read() as u32 >> LEVEL_SHIFT {
+        0 => LEVELS,
+        n => (1 << n) - 1,
+    };
+    !LEVELS | levels
+}
+
+/// Set specific interrupts
+///
+/// The enabled levels are rounded down to consecutive levels from `0`, as BASEPRI can only mask
+/// the levels whose priority is lower than a threshold.
+/// # Safety
+#[cfg(has_basepri)]
+#[inline]
+pub unsafe fn set_mask(mask: u32) {
+    use cortex_m::register::basepri;
+
+    let levels = if mask & !LEVELS == 0 {
+        0
+    } else {
+        (mask & LEVELS).trailing_ones()
+    };
+    if levels == 0 {
+        interrupt::disable();
+        basepri::write(0);
+    } else {
+        let threshold = if levels >= LEVELS.count_ones() {
+            0
+        } else {
+            levels << LEVEL_SHIFT
+        };
+        basepri::write(threshold as u8);
+        interrupt::enable();
+    }
+}
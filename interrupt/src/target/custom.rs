@@ -0,0 +1,87 @@
+/// Register interrupt function on custom targets.
+///
+/// # Usage
+///
+/// To register the function, we first depend on `interrupt` in `Cargo.toml`:
+/// ```toml
+/// [dependencies]
+/// interrupt = { version = "0.1", features = ["custom"] }
+/// ```
+///
+/// Then, we register the function in `src/main.rs`:
+/// ```rust
+/// # use core::sync::atomic::{AtomicU32, Ordering};
+/// # static REGISTER: AtomicU32 = AtomicU32::new(0);
+/// use interrupt::register_interrupt;
+///
+/// fn interrupt_get_mask() -> u32 {
+///     REGISTER.load(Ordering::Relaxed)
+/// }
+/// fn interrupt_set_mask(mask: u32) {
+///     REGISTER.store(mask, Ordering::Relaxed)
+/// }
+/// register_interrupt!(interrupt_get_mask, interrupt_set_mask);
+/// # fn main() {}
+/// ```
+///
+/// # Addition
+/// You can also register `fn rust_interrupt_get() -> u32;` and `fn rust_interrupt_set(mask: u32);`
+/// in the Static-link Library or Dynamic-link Library.
+///
+/// With the `backend` feature, these functions are not linked, register the interrupt controller by
+/// `set_backend` instead. Entering a critical section before it is registered panics,
+/// rather than running without masking any interrupt.
+#[macro_export]
+#[cfg_attr(docsrs, doc(cfg(feature = "custom")))]
+macro_rules! register_interrupt {
+    ($get:path, $set:path) => {
+        #[no_mangle]
+        extern "C" fn rust_interrupt_get() -> u32 {
+            unsafe { $get() }
+        }
+        #[no_mangle]
+        extern "C" fn rust_interrupt_set(mask: u32) {
+            let _ = unsafe { $set(mask) };
+        }
+    };
+}
+
+#[cfg(not(feature = "backend"))]
+extern "C" {
+    fn rust_interrupt_get() -> u32;
+    fn rust_interrupt_set(mask: u32);
+}
+
+/// Get specific interrupts and returns the current setting
+#[cfg(not(feature = "backend"))]
+#[inline]
+pub fn get_mask() -> u32 {
+    unsafe { rust_interrupt_get() }
+}
+
+/// Set specific interrupts
+/// # Safety
+#[cfg(not(feature = "backend"))]
+#[inline]
+pub unsafe fn set_mask(mask: u32) {
+    rust_interrupt_set(mask)
+}
+
+/// Get specific interrupts and returns the current setting
+///
+/// Panics, no interrupt controller is linked until a backend is registered.
+#[cfg(feature = "backend")]
+#[inline]
+pub fn get_mask() -> u32 {
+    panic!("interrupt backend is not registered by `set_backend`")
+}
+
+/// Set specific interrupts
+/// # Safety
+///
+/// Panics, no interrupt controller is linked until a backend is registered.
+#[cfg(feature = "backend")]
+#[inline]
+pub unsafe fn set_mask(_mask: u32) {
+    panic!("interrupt backend is not registered by `set_backend`")
+}
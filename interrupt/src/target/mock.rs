@@ -0,0 +1,86 @@
+static mut MOCK: u32 = 0;
+
+/// Get specific interrupts and returns the current setting
+#[inline]
+pub fn get_mask() -> u32 {
+    unsafe { MOCK }
+}
+
+/// Set specific interrupts
+/// # Safety
+#[inline]
+pub unsafe fn set_mask(mask: u32) {
+    MOCK = mask
+}
+
+#[cfg(feature = "mock-lock")]
+mod lock {
+    use core::cell::Cell;
+    #[cfg(not(loom))]
+    use core::sync::atomic::{AtomicUsize, Ordering};
+    #[cfg(loom)]
+    use loom::sync::atomic::{AtomicUsize, Ordering};
+    #[cfg(not(loom))]
+    use std::thread;
+    #[cfg(loom)]
+    use loom::thread;
+
+    /// Thread which owns the critical section, `0` if none.
+    #[cfg(not(loom))]
+    static OWNER: AtomicUsize = AtomicUsize::new(0);
+    #[cfg(not(loom))]
+    std::thread_local! {
+        static DEPTH: Cell<usize> = const { Cell::new(0) };
+    }
+    // The threads of loom share one OS thread, and its statics are reset for every execution.
+    #[cfg(loom)]
+    loom::lazy_static! {
+        static ref OWNER: AtomicUsize = AtomicUsize::new(0);
+    }
+    #[cfg(loom)]
+    loom::thread_local! {
+        static DEPTH: Cell<usize> = Cell::new(0);
+    }
+
+    fn thread_id() -> usize {
+        DEPTH.with(|x| x as *const _ as usize)
+    }
+
+    /// Enter the critical section, blocking other threads until the outermost `release`.
+    pub fn acquire() {
+        let id = thread_id();
+        if OWNER.load(Ordering::Relaxed) != id {
+            while OWNER
+                .compare_exchange_weak(0, id, Ordering::Acquire, Ordering::Relaxed)
+                .is_err()
+            {
+                thread::yield_now();
+            }
+        }
+        DEPTH.with(|x| x.set(x.get() + 1));
+    }
+
+    /// Exit the critical section.
+    pub fn release() {
+        let depth = DEPTH.with(|x| {
+            let depth = x.get() - 1;
+            x.set(depth);
+            depth
+        });
+        if depth == 0 {
+            OWNER.store(0, Ordering::Release);
+        }
+    }
+}
+#[cfg(feature = "mock-lock")]
+pub use lock::{acquire, release};
+
+/// Enter the critical section
+#[cfg(not(feature = "mock-lock"))]
+#[inline]
+pub fn acquire() {}
+
+/// Exit the critical section
+#[cfg(not(feature = "mock-lock"))]
+#[inline]
+pub fn release() {}
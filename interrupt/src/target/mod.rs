@@ -0,0 +1,71 @@
+// The critical section of `critical-section` provider doesn't need the lock of target.
+#![cfg_attr(cs_delegate, allow(dead_code, unused_imports))]
+
+cfg_if::cfg_if! {
+    if #[cfg(feature = "custom")] {
+        #[path = "custom.rs"]
+        #[allow(clippy::module_inception)]
+        mod target;
+    } else if #[cfg(all(feature = "sim", not(target_os = "none")))] {
+        #[path = "sim.rs"]
+        pub mod sim;
+        use sim as target;
+    } else if #[cfg(not(target_os = "none"))] {
+        // Disable interrupt if using an OS
+        #[path = "mock.rs"]
+        #[allow(clippy::module_inception)]
+        mod target;
+    } else if #[cfg(target_arch = "arm")] {
+        #[path = "cortex_m.rs"]
+        mod target;
+    } else if #[cfg(any(target_arch = "riscv32", target_arch = "riscv64"))] {
+        #[path = "riscv.rs"]
+        mod target;
+    } else if #[cfg(target_arch = "msp430")] {
+        #[path = "msp430.rs"]
+        mod target;
+    } else if #[cfg(target_arch = "xtensa")] {
+        #[path = "xtensa.rs"]
+        mod target;
+    } else {
+        compile_error!("not support");
+    }
+}
+
+#[cfg(not(feature = "backend"))]
+pub use target::{get_mask, set_mask};
+
+/// Get specific interrupts and returns the current setting
+#[cfg(feature = "backend")]
+#[inline]
+pub fn get_mask() -> u32 {
+    match crate::backend::backend() {
+        Some(backend) => backend.get_mask(),
+        None => target::get_mask(),
+    }
+}
+
+/// Set specific interrupts
+/// # Safety
+#[cfg(feature = "backend")]
+#[inline]
+pub unsafe fn set_mask(mask: u32) {
+    match crate::backend::backend() {
+        Some(backend) => backend.set_mask(mask),
+        None => target::set_mask(mask),
+    }
+}
+
+cfg_if::cfg_if! {
+    if #[cfg(all(not(feature = "custom"), not(target_os = "none")))] {
+        pub(crate) use target::{acquire, release};
+    } else {
+        /// Enter the critical section
+        #[inline]
+        pub(crate) fn acquire() {}
+
+        /// Exit the critical section
+        #[inline]
+        pub(crate) fn release() {}
+    }
+}
@@ -0,0 +1,155 @@
+//! Simulated interrupt controller for deterministic host testing.
+//!
+//! Every thread is a core with 32 numbered interrupts, bit `n` of mask enables interrupt `n`.
+//! A pending interrupt is dispatched as soon as it is enabled, a lower number preempts a higher one.
+//!
+//! It replaces the mock target of hosted targets, so the `mock-lock` feature has no effect with it.
+//! The threads don't exclude each other in critical section, only the interrupts of a thread are masked.
+
+use core::cell::RefCell;
+use std::rc::Rc;
+use std::vec::Vec;
+
+/// Points where a preemption can be injected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Point {
+    /// Just before entering a critical section.
+    Enter,
+    /// Just after exiting a critical section.
+    Exit,
+    /// Reached by `point(Point::Custom(id))` in user code.
+    Custom(u32),
+}
+
+struct Sim {
+    mask: u32,
+    pending: u32,
+    handlers: [Option<Rc<dyn Fn()>>; 32],
+    preempts: Vec<(Point, usize, u32)>,
+}
+impl Sim {
+    const NONE_HANDLER: Option<Rc<dyn Fn()>> = None;
+    const fn new() -> Self {
+        Self {
+            mask: u32::MAX,
+            pending: 0,
+            handlers: [Self::NONE_HANDLER; 32],
+            preempts: Vec::new(),
+        }
+    }
+}
+
+std::thread_local! {
+    static SIM: RefCell<Sim> = const { RefCell::new(Sim::new()) };
+}
+
+/// Dispatches pending interrupts which are enabled.
+fn dispatch() {
+    loop {
+        let next = SIM.with(|sim| {
+            let mut sim = sim.borrow_mut();
+            let ready = sim.pending & sim.mask;
+            if ready == 0 {
+                return None;
+            }
+            let irq = ready.trailing_zeros();
+            sim.pending &= !(1 << irq);
+            let prev = sim.mask;
+            // Only the interrupts with higher priority can preempt the handler.
+            sim.mask &= (1 << irq) - 1;
+            Some((sim.handlers[irq as usize].clone(), prev))
+        });
+        let (handler, prev) = match next {
+            Some(v) => v,
+            None => return,
+        };
+        if let Some(handler) = handler {
+            handler();
+        }
+        SIM.with(|sim| sim.borrow_mut().mask = prev);
+    }
+}
+
+/// Registers the handler of interrupt `irq`, and returns the previous one.
+pub fn register(irq: u32, handler: impl Fn() + 'static) -> Option<Rc<dyn Fn()>> {
+    assert!(irq < 32);
+    SIM.with(|sim| sim.borrow_mut().handlers[irq as usize].replace(Rc::new(handler)))
+}
+
+/// Removes the handler of interrupt `irq`.
+pub fn unregister(irq: u32) -> Option<Rc<dyn Fn()>> {
+    assert!(irq < 32);
+    SIM.with(|sim| sim.borrow_mut().handlers[irq as usize].take())
+}
+
+/// Raises interrupt `irq` as pending. It is dispatched immediately if enabled.
+pub fn pend(irq: u32) {
+    assert!(irq < 32);
+    SIM.with(|sim| sim.borrow_mut().pending |= 1 << irq);
+    dispatch();
+}
+
+/// Returns the pending interrupts.
+pub fn pending() -> u32 {
+    SIM.with(|sim| sim.borrow().pending)
+}
+
+/// Raises interrupt `irq` when `point` is reached for the `nth` time (from 0).
+pub fn preempt_at(point: Point, nth: usize, irq: u32) {
+    assert!(irq < 32);
+    SIM.with(|sim| sim.borrow_mut().preempts.push((point, nth, irq)));
+}
+
+/// Reaches a point, and raises the interrupts injected by `preempt_at`.
+pub fn point(point: Point) {
+    let irqs = SIM.with(|sim| {
+        let mut irqs = 0;
+        sim.borrow_mut().preempts.retain_mut(|(p, nth, irq)| {
+            if *p != point {
+                true
+            } else if *nth == 0 {
+                irqs |= 1 << *irq;
+                false
+            } else {
+                *nth -= 1;
+                true
+            }
+        });
+        irqs
+    });
+    if irqs != 0 {
+        SIM.with(|sim| sim.borrow_mut().pending |= irqs);
+        dispatch();
+    }
+}
+
+/// Resets the simulator of current thread.
+pub fn reset() {
+    SIM.with(|sim| *sim.borrow_mut() = Sim::new());
+}
+
+/// Get specific interrupts and returns the current setting
+#[inline]
+pub fn get_mask() -> u32 {
+    SIM.with(|sim| sim.borrow().mask)
+}
+
+/// Set specific interrupts, and dispatch the pending interrupts which are enabled.
+/// # Safety
+#[inline]
+pub unsafe fn set_mask(mask: u32) {
+    SIM.with(|sim| sim.borrow_mut().mask = mask);
+    dispatch();
+}
+
+/// Enter the critical section
+#[inline]
+pub fn acquire() {
+    point(Point::Enter);
+}
+
+/// Exit the critical section
+#[inline]
+pub fn release() {
+    point(Point::Exit);
+}
@@ -0,0 +1,61 @@
+#[cfg(all(feature = "backend", not(feature = "custom")))]
+#[test]
+fn test() {
+    use core::sync::atomic::{AtomicU32, Ordering};
+    use interrupt::*;
+
+    struct MyBackend(AtomicU32);
+    impl Backend for MyBackend {
+        fn get_mask(&self) -> u32 {
+            self.0.load(Ordering::Relaxed)
+        }
+        unsafe fn set_mask(&self, mask: u32) {
+            self.0.store(mask, Ordering::Relaxed)
+        }
+    }
+    static BACKEND: MyBackend = MyBackend(AtomicU32::new(0b10));
+    static OTHER: MyBackend = MyBackend(AtomicU32::new(0));
+
+    assert!(backend().is_none());
+    unsafe { set_mask(1) };
+    assert_eq!(get_mask(), 1);
+
+    assert!(set_backend(&BACKEND).is_ok());
+    assert!(set_backend(&OTHER).is_err());
+    assert!(backend().is_some());
+    assert_eq!(get_mask(), 0b10);
+    let cs = CriticalSection::new();
+    assert_eq!(BACKEND.get_mask(), 0);
+    drop(cs);
+    assert_eq!(BACKEND.get_mask(), 0b10);
+    assert_eq!(OTHER.get_mask(), 0);
+}
+
+#[cfg(all(feature = "backend", feature = "custom"))]
+#[test]
+fn custom() {
+    use core::sync::atomic::{AtomicU32, Ordering};
+    use interrupt::*;
+
+    struct MyBackend(AtomicU32);
+    impl Backend for MyBackend {
+        fn get_mask(&self) -> u32 {
+            self.0.load(Ordering::Relaxed)
+        }
+        unsafe fn set_mask(&self, mask: u32) {
+            self.0.store(mask, Ordering::Relaxed)
+        }
+    }
+    static BACKEND: MyBackend = MyBackend(AtomicU32::new(0b10));
+
+    // Links without `register_interrupt!`, nothing masks interrupts until a backend is registered.
+    assert!(std::panic::catch_unwind(get_mask).is_err());
+    assert!(std::panic::catch_unwind(CriticalSection::new).is_err());
+
+    assert!(set_backend(&BACKEND).is_ok());
+    assert_eq!(get_mask(), 0b10);
+    let cs = CriticalSection::new();
+    assert_eq!(BACKEND.get_mask(), 0);
+    drop(cs);
+    assert_eq!(BACKEND.get_mask(), 0b10);
+}
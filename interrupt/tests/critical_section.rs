@@ -0,0 +1,39 @@
+#[cfg(all(feature = "critical-section-impl", not(feature = "custom")))]
+#[test]
+fn provider() {
+    use interrupt::*;
+
+    unsafe { set_mask(1) };
+    critical_section::with(|_| {
+        assert_eq!(get_mask(), 0);
+        critical_section::with(|_| assert_eq!(get_mask(), 0));
+        assert_eq!(get_mask(), 0);
+    });
+    assert_eq!(get_mask(), 1);
+}
+
+#[cfg(all(feature = "critical-section", not(feature = "critical-section-impl")))]
+#[test]
+fn delegate() {
+    use core::sync::atomic::{AtomicUsize, Ordering::SeqCst};
+    use interrupt::*;
+
+    static DEPTH: AtomicUsize = AtomicUsize::new(0);
+    struct MyCriticalSection;
+    critical_section::set_impl!(MyCriticalSection);
+    unsafe impl critical_section::Impl for MyCriticalSection {
+        unsafe fn acquire() {
+            DEPTH.fetch_add(1, SeqCst);
+        }
+        unsafe fn release(_: ()) {
+            DEPTH.fetch_sub(1, SeqCst);
+        }
+    }
+
+    let cs = CriticalSection::new();
+    assert_eq!(DEPTH.load(SeqCst), 1);
+    critical_section::with(|_| assert_eq!(DEPTH.load(SeqCst), 2));
+    let _token = cs.token();
+    drop(cs);
+    assert_eq!(DEPTH.load(SeqCst), 0);
+}
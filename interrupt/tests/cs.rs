@@ -1,11 +1,28 @@
+// The mask isn't touched when the critical section is delegated to the application.
+#![cfg(all(
+    not(feature = "custom"),
+    any(not(feature = "critical-section"), feature = "critical-section-impl")
+))]
+use interrupt::*;
+
+// Both cases share the global mask of the mock target, so they run in one test.
 #[test]
 fn test() {
-    use interrupt::*;
-
     unsafe { set_mask(1) };
     assert_eq!(get_mask(), 1);
     let cs = CriticalSection::new();
     assert_eq!(get_mask(), 0);
     drop(cs);
     assert_eq!(get_mask(), 1);
+
+    assert!(!in_critical_section());
+    let cs1 = CriticalSection::new();
+    assert!(in_critical_section());
+    let cs2 = CriticalSection::new();
+    assert_eq!(nesting_depth(), 2);
+    free(|_| assert_eq!(nesting_depth(), 3));
+    drop(cs2);
+    assert_eq!(nesting_depth(), 1);
+    drop(cs1);
+    assert!(!in_critical_section());
 }
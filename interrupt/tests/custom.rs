@@ -1,4 +1,4 @@
-#[cfg(feature = "custom")]
+#[cfg(all(feature = "custom", not(feature = "backend")))]
 #[test]
 fn test() {
     use interrupt::*;
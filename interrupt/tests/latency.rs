@@ -0,0 +1,53 @@
+#[cfg(feature = "latency")]
+#[test]
+fn test() {
+    use core::panic::Location;
+    use core::sync::atomic::{AtomicU64, Ordering};
+    use interrupt::*;
+
+    struct MyClock(AtomicU64);
+    impl Clock for MyClock {
+        fn now(&self) -> u64 {
+            self.0.load(Ordering::Relaxed)
+        }
+    }
+    static CLOCK: MyClock = MyClock(AtomicU64::new(0));
+    fn tick(n: u64) {
+        CLOCK.0.fetch_add(n, Ordering::Relaxed);
+    }
+
+    // Not measured without clock.
+    drop(CriticalSection::new());
+    assert!(set_clock(&CLOCK).is_ok());
+    assert!(set_clock(&CLOCK).is_err());
+
+    fn masked(n: u64) -> u32 {
+        let line = line!() + 1;
+        let _cs = CriticalSection::new();
+        tick(n);
+        line
+    }
+    for n in [3, 5, 1] {
+        masked(n);
+    }
+    let line = line!() + 1;
+    free(|_| tick(7));
+
+    let stats = latency_of(Location::caller());
+    assert!(stats.is_none());
+    let mut sites = 0;
+    for_each_latency(|stats| {
+        sites += 1;
+        assert_eq!(stats.location.file(), file!());
+        if stats.location.line() == line {
+            assert_eq!((stats.count, stats.max, stats.total), (1, 7, 7));
+        } else {
+            assert_eq!(stats.location.line(), masked(0));
+            assert_eq!((stats.count, stats.max, stats.total), (3, 5, 9));
+        }
+    });
+    assert_eq!(sites, 2);
+
+    reset_latency();
+    for_each_latency(|_| unreachable!());
+}
@@ -0,0 +1,25 @@
+#[cfg(all(feature = "mock-lock", not(feature = "custom"), not(feature = "sim")))]
+#[test]
+fn test() {
+    use core::cell::Cell;
+    use interrupt::*;
+    use std::thread;
+
+    static COUNT: Mutex<Cell<usize>> = Mutex::new(Cell::new(0));
+    thread::scope(|s| {
+        for _ in 0..8 {
+            s.spawn(|| {
+                for _ in 0..100 {
+                    free(|cs| {
+                        let count = COUNT.borrow(*cs);
+                        let val = count.get();
+                        thread::yield_now();
+                        // reentrant
+                        free(|_| count.set(val + 1));
+                    });
+                }
+            });
+        }
+    });
+    free(|cs| assert_eq!(COUNT.borrow(*cs).get(), 800));
+}
@@ -0,0 +1,27 @@
+// The mask isn't touched when the critical section is delegated to the application.
+#[cfg(all(
+    not(feature = "custom"),
+    any(not(feature = "critical-section"), feature = "critical-section-impl")
+))]
+#[test]
+fn test() {
+    use interrupt::*;
+
+    unsafe { set_mask(0b111) };
+    let cs1 = unsafe { CriticalSection::with_mask(0b100) };
+    assert_eq!(get_mask(), 0b011);
+    let cs2 = unsafe { CriticalSection::with_mask(0b010) };
+    assert_eq!(get_mask(), 0b001);
+    drop(cs2);
+    assert_eq!(get_mask(), 0b011);
+    drop(cs1);
+    assert_eq!(get_mask(), 0b111);
+
+    assert_eq!(default_mask(), MASK_ALL);
+    assert_eq!(unsafe { set_default_mask(0b110) }, MASK_ALL);
+    let cs = CriticalSection::new();
+    assert_eq!(get_mask(), 0b001);
+    drop(cs);
+    assert_eq!(get_mask(), 0b111);
+    unsafe { set_default_mask(MASK_ALL) };
+}
@@ -0,0 +1,35 @@
+#[cfg(all(feature = "multicore", not(feature = "critical-section")))]
+#[test]
+fn test() {
+    use core::cell::Cell;
+    use interrupt::*;
+    use std::thread;
+
+    fn core_id() -> usize {
+        std::thread_local! {
+            static ID: u8 = const { 0 };
+        }
+        ID.with(|x| x as *const _ as usize)
+    }
+    static LOCK: AtomicCoreLock = AtomicCoreLock::new(core_id);
+    static COUNT: Mutex<Cell<usize>> = Mutex::new(Cell::new(0));
+    assert!(set_core_lock(&LOCK).is_ok());
+    assert!(set_core_lock(&LOCK).is_err());
+
+    thread::scope(|s| {
+        for _ in 0..4 {
+            s.spawn(|| {
+                for _ in 0..1000 {
+                    let cs = CriticalSection::new();
+                    let count = COUNT.borrow(*cs);
+                    let val = count.get();
+                    thread::yield_now();
+                    // Reentrant on the same core.
+                    free(|cs| assert_eq!(COUNT.borrow(*cs).get(), val));
+                    count.set(val + 1);
+                }
+            });
+        }
+    });
+    assert_eq!(free(|cs| COUNT.borrow(*cs).get()), 4000);
+}
@@ -0,0 +1,57 @@
+use core::cell::RefCell;
+use interrupt::*;
+
+#[cfg(all(feature = "custom", not(feature = "backend")))]
+mod custom {
+    use core::sync::atomic::{AtomicU32, Ordering};
+
+    static MOCK: AtomicU32 = AtomicU32::new(0);
+    fn my_get_mask() -> u32 {
+        MOCK.load(Ordering::Relaxed)
+    }
+    fn my_set_mask(mask: u32) {
+        MOCK.store(mask, Ordering::Relaxed)
+    }
+    interrupt::register_interrupt!(my_get_mask, my_set_mask);
+}
+
+#[cfg(all(feature = "custom", feature = "backend"))]
+mod backend {
+    use core::sync::atomic::{AtomicU32, Ordering};
+
+    struct Mock(AtomicU32);
+    impl interrupt::Backend for Mock {
+        fn get_mask(&self) -> u32 {
+            self.0.load(Ordering::Relaxed)
+        }
+        unsafe fn set_mask(&self, mask: u32) {
+            self.0.store(mask, Ordering::Relaxed)
+        }
+    }
+    static MOCK: Mock = Mock(AtomicU32::new(0));
+
+    pub fn init() {
+        let _ = interrupt::set_backend(&MOCK);
+    }
+}
+
+#[cfg(all(feature = "critical-section", not(feature = "critical-section-impl")))]
+mod provider {
+    struct MyCriticalSection;
+    critical_section::set_impl!(MyCriticalSection);
+    unsafe impl critical_section::Impl for MyCriticalSection {
+        unsafe fn acquire() {}
+        unsafe fn release(_: ()) {}
+    }
+}
+
+#[test]
+fn test() {
+    #[cfg(all(feature = "custom", feature = "backend"))]
+    backend::init();
+    static DATA: Mutex<RefCell<usize>> = Mutex::new(RefCell::new(1));
+    free(|cs| {
+        *DATA.borrow_ref_mut(*cs) += 1;
+        assert_eq!(*DATA.borrow_ref(*cs), 2);
+    });
+}
@@ -0,0 +1,154 @@
+#![cfg(all(feature = "sim", not(feature = "custom")))]
+use ach_array::Array;
+use ach_linked::doubly::{LinkedList, Node};
+use core::cell::{Cell, RefCell};
+use interrupt::sim::{self, Point};
+use interrupt::*;
+use std::rc::Rc;
+use std::vec::Vec;
+
+#[test]
+fn dispatch() {
+    let log = Rc::new(RefCell::new(Vec::new()));
+    for irq in [1, 2] {
+        let log = log.clone();
+        sim::register(irq, move || log.borrow_mut().push(irq));
+    }
+
+    sim::pend(2);
+    assert_eq!(*log.borrow(), [2]);
+
+    let cs = CriticalSection::new();
+    sim::pend(2);
+    sim::pend(1);
+    assert_eq!(sim::pending(), 0b110);
+    assert_eq!(*log.borrow(), [2]);
+    drop(cs);
+    assert_eq!(sim::pending(), 0);
+    assert_eq!(*log.borrow(), [2, 1, 2]);
+
+    unsafe { set_mask(0b010) };
+    sim::pend(2);
+    sim::pend(1);
+    assert_eq!(*log.borrow(), [2, 1, 2, 1]);
+    unsafe { set_mask(MASK_ALL) };
+    assert_eq!(*log.borrow(), [2, 1, 2, 1, 2]);
+}
+
+#[test]
+fn preempt() {
+    let log = Rc::new(RefCell::new(Vec::new()));
+    {
+        let log = log.clone();
+        sim::register(1, move || log.borrow_mut().push(1));
+    }
+    {
+        let log = log.clone();
+        sim::register(3, move || {
+            log.borrow_mut().push(3);
+            // interrupt 1 preempts interrupt 3, but interrupt 5 doesn't.
+            sim::pend(5);
+            sim::pend(1);
+            log.borrow_mut().push(30);
+        });
+    }
+    {
+        let log = log.clone();
+        sim::register(5, move || log.borrow_mut().push(5));
+    }
+
+    sim::pend(3);
+    assert_eq!(*log.borrow(), [3, 1, 30, 5]);
+    assert_eq!(get_mask(), MASK_ALL);
+}
+
+#[test]
+fn point() {
+    static DATA: Mutex<RefCell<Vec<u32>>> = Mutex::new(RefCell::new(Vec::new()));
+    fn with<R>(f: impl FnOnce(&mut Vec<u32>) -> R) -> R {
+        let cs = CriticalSection::new();
+        let r = f(&mut DATA.borrow_ref_mut(*cs));
+        r
+    }
+    let count = Rc::new(Cell::new(0));
+    {
+        let count = count.clone();
+        sim::register(0, move || {
+            count.set(count.get() + 1);
+            with(|data| data.push(count.get()));
+        });
+    }
+    with(|data| data.extend([10, 20]));
+
+    // The interrupt raised while popping is delayed until the critical section exit.
+    sim::preempt_at(Point::Custom(0), 0, 0);
+    {
+        let cs = CriticalSection::new();
+        let mut data = DATA.borrow(*cs).borrow_mut();
+        sim::point(Point::Custom(0));
+        assert_eq!(sim::pending(), 1);
+        assert_eq!(data.pop(), Some(20));
+    }
+    assert_eq!(count.get(), 1);
+    assert_eq!(with(|data| data.clone()), [10, 1]);
+
+    // Enters the critical section twice, and the interrupt preempts before the second one.
+    sim::preempt_at(Point::Enter, 1, 0);
+    let a = with(|data| data.pop());
+    assert_eq!(count.get(), 1);
+    let b = with(|data| data.pop());
+    assert_eq!(count.get(), 2);
+    assert_eq!((a, b), (Some(1), Some(2)));
+
+    sim::preempt_at(Point::Exit, 0, 0);
+    with(|data| data.push(30));
+    assert_eq!(count.get(), 3);
+    assert_eq!(with(|data| data.clone()), [10, 30, 3]);
+
+    sim::reset();
+    assert_eq!(get_mask(), MASK_ALL);
+    sim::pend(0);
+    assert_eq!(count.get(), 3);
+}
+
+#[test]
+fn array() {
+    static ARRAY: Array<usize, 2> = Array::new();
+    sim::reset();
+    sim::register(0, || ARRAY.push(20).map(|_| ()).unwrap());
+    ARRAY[1].set(10).unwrap();
+
+    // The interrupt pushes to the first slot, after the pop found it empty.
+    sim::preempt_at(Point::Exit, 0, 0);
+    assert_eq!(ARRAY.pop(), Some(10));
+    assert_eq!(ARRAY.pop(), Some(20));
+    assert_eq!(ARRAY.pop(), None);
+}
+
+#[test]
+fn sorted() {
+    static LIST: LinkedList<usize> = LinkedList::new();
+    sim::reset();
+    let nodes: Vec<_> = (0..12)
+        .map(|i| Rc::new(RefCell::new(Some(Box::pin(Node::new(i * 2))))))
+        .collect();
+    for node in nodes.iter() {
+        let mut node = node.borrow_mut();
+        LIST.push_sorted_by_key(node.as_mut().unwrap().as_mut(), |x| *x);
+    }
+    {
+        // The last node compared by the first critical section of the walk.
+        let node = nodes[7].clone();
+        sim::register(0, move || drop(node.borrow_mut().take()));
+    }
+
+    // The interrupt frees a node between two critical sections of the walk, so it restarts.
+    sim::preempt_at(Point::Enter, 1, 0);
+    let mut node = Box::pin(Node::new(21));
+    LIST.push_sorted_by_key(node.as_mut(), |x| *x);
+    assert!(nodes[7].borrow().is_none());
+
+    let mut vals = Vec::new();
+    LIST.for_each(|x| vals.push(*x));
+    assert_eq!(vals, [0, 2, 4, 6, 8, 10, 12, 16, 18, 20, 21, 22]);
+}
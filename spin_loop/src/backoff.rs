@@ -0,0 +1,89 @@
+use crate::spin;
+
+/// Strategy of waiting before the next attempt.
+pub trait Backoff {
+    /// Waits before the next attempt. Returns false to give up.
+    fn snooze(&mut self) -> bool;
+}
+
+/// A user-provided wait function, returns false to give up.
+impl<F: FnMut() -> bool> Backoff for F {
+    fn snooze(&mut self) -> bool {
+        self()
+    }
+}
+
+/// Calls `spin` before every attempt, and never gives up.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Spin;
+impl Backoff for Spin {
+    fn snooze(&mut self) -> bool {
+        spin();
+        true
+    }
+}
+
+/// Spins `2^step` times before each attempt, and calls `spin` once the step reaches the limit.
+#[derive(Debug, Clone, Copy)]
+pub struct Exponential {
+    step: u32,
+    spin_limit: u32,
+}
+impl Default for Exponential {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl Exponential {
+    pub const SPIN_LIMIT: u32 = 6;
+    pub const fn new() -> Self {
+        Self::with_limit(Self::SPIN_LIMIT)
+    }
+    pub const fn with_limit(spin_limit: u32) -> Self {
+        Self {
+            step: 0,
+            spin_limit,
+        }
+    }
+    /// Starts spinning from the first step again.
+    pub fn reset(&mut self) {
+        self.step = 0;
+    }
+}
+impl Backoff for Exponential {
+    fn snooze(&mut self) -> bool {
+        if self.step < self.spin_limit && !cfg!(loom) {
+            for _ in 0..1u32 << self.step {
+                core::hint::spin_loop();
+            }
+            self.step += 1;
+        } else {
+            spin();
+        }
+        true
+    }
+}
+
+/// Gives up after `limit` waits of the inner backoff.
+#[derive(Debug, Clone, Copy)]
+pub struct Bounded<B> {
+    inner: B,
+    remaining: usize,
+}
+impl<B: Backoff> Bounded<B> {
+    pub const fn new(inner: B, limit: usize) -> Self {
+        Self {
+            inner,
+            remaining: limit,
+        }
+    }
+}
+impl<B: Backoff> Backoff for Bounded<B> {
+    fn snooze(&mut self) -> bool {
+        if self.remaining == 0 {
+            return false;
+        }
+        self.remaining -= 1;
+        self.inner.snooze()
+    }
+}
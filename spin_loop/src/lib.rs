@@ -3,9 +3,40 @@
 #[cfg(not(target_os = "none"))]
 extern crate std;
 
+mod backoff;
+
+pub use backoff::*;
+
+use core::sync::atomic::{AtomicUsize, Ordering::Relaxed};
+
+/// Address of the function set by `set_wait`, 0 for the built-in wait.
+static WAIT: AtomicUsize = AtomicUsize::new(0);
+
+/// Sets the function called by `spin`, such as WFE or the yield of RTOS.
+pub fn set_wait(wait: fn()) {
+    WAIT.store(wait as usize, Relaxed);
+}
+
+/// Restores the built-in wait of `spin`.
+pub fn reset_wait() {
+    WAIT.store(0, Relaxed);
+}
+
 pub fn spin() {
-    #[cfg(target_os = "none")]
-    core::hint::spin_loop();
-    #[cfg(not(target_os = "none"))]
-    std::thread::yield_now();
+    // Every spin must be a yield point of loom, or the model never ends.
+    #[cfg(loom)]
+    loom::thread::yield_now();
+    #[cfg(not(loom))]
+    {
+        let wait = WAIT.load(Relaxed);
+        if wait != 0 {
+            // Only stored from a `fn()` by `set_wait`.
+            let wait: fn() = unsafe { core::mem::transmute::<usize, fn()>(wait) };
+            return wait();
+        }
+        #[cfg(target_os = "none")]
+        core::hint::spin_loop();
+        #[cfg(not(target_os = "none"))]
+        std::thread::yield_now();
+    }
 }